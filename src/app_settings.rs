@@ -1,19 +1,39 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
 
+use crate::model::map_builders::MapBuilderKind;
+use crate::model::ModelConstants;
 use crate::ui::UiConstants;
 
+/// How the map is drawn
+#[derive(Serialize, Deserialize, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderMode {
+    /// Font glyphs, as in a terminal
+    #[default]
+    Ascii,
+    /// Sprites from the tileset data file
+    Tileset,
+}
+
+/// Player settings, kept in `settings/app_settings.toml`.
+///
+/// Every field has a serde default so files written by older versions keep loading.
 #[derive(Serialize, Deserialize, Resource, Reflect, Clone)]
 #[reflect(Resource)]
 pub struct AppSettings {
     // Graphics
     /// Fullscreen mode
+    #[serde(default)]
     pub fullscreen: bool,
 
     /// Display size of each tile
+    #[serde(default = "AppSettings::default_tile_size")]
     pub tile_size: u32,
 
     /// View size in tiles
+    #[serde(default = "AppSettings::default_view_size")]
     pub view_size: (u32, u32),
     // Zoom amount of UI
     // pub interface_zoom: f32,
@@ -42,8 +62,23 @@ pub struct AppSettings {
     // pub creature_flash_damage: bool,
 
     // Font
-    // pub font_path: Path,
-    // pub font_height: u32,
+    /// Whether the map is drawn with font glyphs or tileset sprites
+    #[serde(default)]
+    pub render_mode: RenderMode,
+
+    /// Font sheet of 16x16 glyphs used for text, and for the map in ascii mode
+    #[serde(default = "AppSettings::default_font_path")]
+    pub font_path: String,
+
+    /// Size in pixels of one glyph in the font sheet
+    #[serde(default = "AppSettings::default_font_size")]
+    pub font_width: u32,
+    #[serde(default = "AppSettings::default_font_size")]
+    pub font_height: u32,
+
+    /// Tileset data file for tileset mode, relative to the assets folder
+    #[serde(default = "AppSettings::default_tileset_path")]
+    pub tileset_path: String,
 
     // Audio
     // pub sound_effects: bool,
@@ -60,6 +95,29 @@ pub struct AppSettings {
     // Gamepad
 
     // Gameplay
+    /// Size of each dungeon level in tiles, the view scrolls over larger ones
+    #[serde(default = "AppSettings::default_map_size")]
+    pub map_size: (u32, u32),
+
+    /// Generator used for every level, when unset each depth picks its own
+    #[serde(default)]
+    pub map_builder: Option<MapBuilderKind>,
+
+    /// Seed for the dungeon, a random one is rolled every run when unset
+    #[serde(default)]
+    pub seed: Option<u64>,
+
+    /// Seed given with `--seed`, only for this session and never written back
+    #[serde(skip)]
+    pub seed_override: Option<u64>,
+
+    /// Set when the settings file couldn't be read or backed up, so it is never overwritten
+    #[serde(skip)]
+    pub keep_file: bool,
+
+    /// Block diagonal steps that pass between two walls
+    #[serde(default)]
+    pub forbid_diagonal_squeeze: bool,
     // Companions
     // Health Warning
     // Auto-explore
@@ -68,14 +126,87 @@ pub struct AppSettings {
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
-            tile_size: 16,
+            tile_size: Self::default_tile_size(),
             fullscreen: false,
-            view_size: (60, 40),
+            view_size: Self::default_view_size(),
+            render_mode: RenderMode::default(),
+            font_path: Self::default_font_path(),
+            font_width: Self::default_font_size(),
+            font_height: Self::default_font_size(),
+            tileset_path: Self::default_tileset_path(),
+            map_size: Self::default_map_size(),
+            map_builder: None,
+            seed: None,
+            seed_override: None,
+            keep_file: false,
+            forbid_diagonal_squeeze: false,
         }
     }
 }
 
 impl AppSettings {
+    const fn default_tile_size() -> u32 {
+        16
+    }
+
+    const fn default_view_size() -> (u32, u32) {
+        (60, 40)
+    }
+
+    fn default_font_path() -> String {
+        "terminal8x8.png".to_string()
+    }
+
+    const fn default_font_size() -> u32 {
+        8
+    }
+
+    fn default_tileset_path() -> String {
+        "example_tiles.toml".to_string()
+    }
+
+    const fn default_map_size() -> (u32, u32) {
+        (ModelConstants::MAP_WIDTH, ModelConstants::MAP_HEIGHT)
+    }
+
+    /// Smallest map the builders can lay a level out on
+    const MIN_MAP_SIZE: u32 = 20;
+
+    /// Raises a map size too small to build levels on, reporting the change
+    fn validated(mut self) -> Self {
+        let (width, height) = self.map_size;
+        self.map_size = (
+            width.max(Self::MIN_MAP_SIZE),
+            height.max(Self::MIN_MAP_SIZE),
+        );
+        if self.map_size != (width, height) {
+            eprintln!(
+                "map_size {width}x{height} is too small, using {}x{}",
+                self.map_size.0, self.map_size.1
+            );
+        }
+        self
+    }
+
+    #[must_use]
+    pub const fn map_width(&self) -> i32 {
+        self.map_size.0 as i32
+    }
+
+    #[must_use]
+    pub const fn map_height(&self) -> i32 {
+        self.map_size.1 as i32
+    }
+
+    /// The dungeon seed, `--seed` taking precedence over the settings file
+    #[must_use]
+    pub const fn seed(&self) -> Option<u64> {
+        match self.seed_override {
+            Some(seed) => Some(seed),
+            None => self.seed,
+        }
+    }
+
     #[must_use]
     pub const fn fullscreen(&self) -> bool {
         self.fullscreen
@@ -90,15 +221,109 @@ impl AppSettings {
     pub const fn window_height(&self) -> f32 {
         (self.tile_size * self.view_size.1 + UiConstants::LOG_HEIGHT) as f32
     }
+
+    /// Overrides settings from command line arguments, e.g. `--seed 1234`
+    pub fn apply_args(&mut self, args: impl IntoIterator<Item = String>) {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let value = match arg.split_once('=') {
+                Some(("--seed", value)) => Some(value.to_string()),
+                None if arg == "--seed" => args.next(),
+                _ => continue,
+            };
+
+            match value.map(|value| value.parse::<u64>()) {
+                Some(Ok(seed)) => self.seed_override = Some(seed),
+                _ => eprintln!("--seed expects a whole number, ignoring it"),
+            }
+        }
+    }
+}
+
+impl AppSettings {
+    const FILE_NAME: &str = "app_settings";
+    const PATH: &str = "settings";
+    fn file_path() -> PathBuf {
+        let mut file_path = PathBuf::from(Self::PATH);
+        file_path.push(Self::FILE_NAME);
+        file_path.set_extension("toml");
+        file_path
+    }
+
+    /// Loads `settings/app_settings.toml`, writing the defaults there when there is none.
+    ///
+    /// A map size below `MIN_MAP_SIZE` tiles on either side is raised to it.
+    /// A file that can't be read or parsed is reported and the defaults are used instead. A
+    /// malformed file is copied to `app_settings.toml.bak` before anything is saved over it.
+    pub fn load() -> Self {
+        let contents = match fs::read_to_string(Self::file_path()) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                let settings = Self::default();
+                settings.save();
+                return settings;
+            }
+            Err(err) => {
+                eprintln!("Failed to read settings, using defaults: {err}");
+                return Self {
+                    keep_file: true,
+                    ..Self::default()
+                };
+            }
+        };
+
+        match toml::from_str::<Self>(&contents) {
+            Ok(settings) => settings.validated(),
+            Err(err) => {
+                let file_path = Self::file_path();
+                eprintln!(
+                    "Warning: {} is malformed, using default settings: {err}",
+                    file_path.display()
+                );
+
+                let backup = file_path.with_extension("toml.bak");
+                match fs::copy(&file_path, &backup) {
+                    Ok(_) => {
+                        eprintln!("The malformed file was backed up to {}", backup.display());
+                        Self::default()
+                    }
+                    Err(err) => {
+                        eprintln!("Failed to back up settings, they won't be saved: {err}");
+                        Self {
+                            keep_file: true,
+                            ..Self::default()
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Writes the settings to `settings/app_settings.toml`, printing what went wrong if it fails.
+    ///
+    /// Does nothing when `keep_file` is set.
+    pub fn save(&self) {
+        if self.keep_file {
+            return;
+        }
+
+        let contents = match toml::to_string_pretty(self) {
+            Ok(contents) => contents,
+            Err(err) => {
+                eprintln!("Failed to serialize settings: {err}");
+                return;
+            }
+        };
+
+        if let Err(err) =
+            fs::create_dir_all(Self::PATH).and_then(|()| fs::write(Self::file_path(), contents))
+        {
+            eprintln!("Failed to save settings: {err}");
+        }
+    }
 }
 
-// impl AppSettings {
-//     const FILE_NAME: &str = "app_settings";
-//     const PATH: &str = "settings";
-//     fn file_path() -> PathBuf {
-//         let mut file_path = PathBuf::from(Self::PATH);
-//         file_path.push(Self::FILE_NAME);
-//         file_path.set_extension("toml");
-//         file_path
-//     }
-// }
+/// Writes the settings back whenever they are changed in game.
+pub fn save_app_settings(settings: Res<AppSettings>) {
+    settings.save();
+}
@@ -2,8 +2,14 @@ use bevy::prelude::*;
 
 use crate::controller::event_systems::*;
 use crate::controller::events::PlayerAction;
+use crate::controller::resources::{
+    ActionInput, GamepadRepeat, InventoryMenu, Keymap, LogHistory, MenuCursor, MouseHover,
+    PickupChoices, Targeting, Travel,
+};
 use crate::controller::systems::*;
 use crate::AppSet;
+use crate::MenuState;
+use crate::OptionsState;
 use crate::RunningState;
 
 pub struct ControllerPlugin;
@@ -12,14 +18,122 @@ impl Plugin for ControllerPlugin {
         // Register events
         app.add_event::<PlayerAction>();
 
+        // Register resources
+        app.insert_resource(Keymap::load())
+            .init_resource::<ActionInput>()
+            .init_resource::<GamepadRepeat>()
+            .init_resource::<MenuCursor>()
+            .init_resource::<MouseHover>()
+            .init_resource::<Travel>()
+            .init_resource::<PickupChoices>()
+            .init_resource::<Targeting>()
+            .init_resource::<LogHistory>()
+            .init_resource::<InventoryMenu>();
+
         // Register systems
         app.add_systems(
             Update,
-            keyboard_input
+            (
+                read_keyboard,
+                read_gamepad,
+                reset_menu_cursor.run_if(
+                    state_changed::<MenuState>
+                        .or(state_changed::<RunningState>)
+                        .or(state_changed::<OptionsState>),
+                ),
+                read_mouse.run_if(in_state(RunningState::Running)),
+            )
+                .chain()
+                .in_set(AppSet::RecordInput),
+        )
+        .add_systems(
+            Update,
+            gameplay_input
+                .in_set(AppSet::RecordInput)
+                .after(reset_menu_cursor)
+                .run_if(in_state(MenuState::Closed)),
+        )
+        .add_systems(
+            Update,
+            (mouse_travel_input, travel_step)
+                .chain()
+                .in_set(AppSet::RecordInput)
+                .after(read_mouse)
+                .run_if(in_state(MenuState::Closed)),
+        )
+        .add_systems(
+            Update,
+            mouse_targeting_input
+                .in_set(AppSet::RecordInput)
+                .after(read_mouse)
+                .after(targeting_input)
+                .run_if(in_state(MenuState::Targeting)),
+        )
+        .add_systems(
+            Update,
+            pickup_menu_input
+                .in_set(AppSet::RecordInput)
+                .after(reset_menu_cursor)
+                .run_if(in_state(MenuState::PickupItem)),
+        )
+        .add_systems(
+            Update,
+            targeting_input
+                .in_set(AppSet::RecordInput)
+                .after(reset_menu_cursor)
+                .run_if(in_state(MenuState::Targeting)),
+        )
+        .add_systems(
+            Update,
+            inventory_input
+                .in_set(AppSet::RecordInput)
+                .after(reset_menu_cursor)
+                .run_if(in_state(MenuState::Inventory)),
+        )
+        .add_systems(
+            Update,
+            equipment_input
+                .in_set(AppSet::RecordInput)
+                .after(reset_menu_cursor)
+                .run_if(in_state(MenuState::Equipment)),
+        )
+        .add_systems(
+            Update,
+            log_history_input
+                .in_set(AppSet::RecordInput)
+                .after(reset_menu_cursor)
+                .run_if(in_state(MenuState::LogHistory)),
+        )
+        .add_systems(
+            Update,
+            start_menu_input
+                .in_set(AppSet::RecordInput)
+                .after(reset_menu_cursor)
+                .run_if(in_state(RunningState::StartMenu).and(in_state(OptionsState::Closed))),
+        )
+        .add_systems(
+            Update,
+            pause_menu_input
+                .in_set(AppSet::RecordInput)
+                .after(reset_menu_cursor)
+                .run_if(in_state(RunningState::Paused).and(in_state(OptionsState::Closed))),
+        )
+        .add_systems(
+            Update,
+            options_input
+                .in_set(AppSet::RecordInput)
+                .after(reset_menu_cursor)
+                .run_if(in_state(OptionsState::Open)),
+        )
+        .add_systems(
+            Update,
+            game_over_input
                 .in_set(AppSet::RecordInput)
-                .run_if(in_state(RunningState::Running)),
+                .after(reset_menu_cursor)
+                .run_if(in_state(RunningState::GameOver)),
         )
         // .add_systems(Update, handle_player_actions.in_set(AppSet::Update))
-        .add_observer(handle_player_actions);
+        .add_observer(handle_player_actions)
+        .add_observer(handle_item_actions);
     }
 }
@@ -0,0 +1,275 @@
+use bevy::{ecs::query::QueryData, prelude::*};
+use bracket_lib::prelude::{DistanceAlg, Point};
+
+use crate::controller::events::PlayerAction;
+use crate::controller::resources::{PickupChoices, Targeting};
+use crate::model::components::{
+    Consumable, Equippable, Equipped, InBackpack, Item, MapLevel, Name, Player, Position, Ranged,
+};
+use crate::model::events::ItemUsed;
+use crate::model::resources::{Dungeon, GameLog, LogCategory, Map};
+use crate::model::systems::PlayerTurn;
+use crate::model::ModelConstants;
+use crate::MenuState;
+
+#[derive(QueryData)]
+pub struct ItemData {
+    entity: Entity,
+    name: &'static Name,
+    position: Option<&'static Position>,
+    backpack: Option<&'static InBackpack>,
+    equippable: Option<&'static Equippable>,
+    equipped: Option<&'static Equipped>,
+    ranged: Option<&'static Ranged>,
+    consumable: Has<Consumable>,
+}
+
+impl ItemDataItem<'_> {
+    fn carried_by(&self, owner: Entity) -> bool {
+        self.backpack
+            .is_some_and(|backpack| backpack.owner == owner)
+    }
+
+    fn equipped_by(&self, owner: Entity) -> bool {
+        self.equipped
+            .is_some_and(|equipped| equipped.owner == owner)
+    }
+}
+
+pub fn handle_item_actions(
+    player_action_trigger: Trigger<PlayerAction>,
+    mut commands: Commands,
+    mut player_turn: PlayerTurn,
+    mut game_log: ResMut<GameLog>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+    mut pickup_choices: ResMut<PickupChoices>,
+    mut targeting: ResMut<Targeting>,
+    mut item_used_events: EventWriter<ItemUsed>,
+    map: Res<Map>,
+    dungeon: Res<Dungeon>,
+    player_query: Query<(Entity, &Position), With<Player>>,
+    item_query: Query<ItemData, With<Item>>,
+) {
+    let Ok((player_entity, player_position)) = player_query.get_single() else {
+        return;
+    };
+
+    if !player_turn.is_turn_of(player_entity) {
+        return;
+    }
+
+    let carried = item_query
+        .iter()
+        .filter(|item| item.carried_by(player_entity))
+        .count();
+
+    match player_action_trigger.event() {
+        PlayerAction::PickupItem(choice) => {
+            let mut items_here: Vec<Entity> = item_query
+                .iter()
+                .filter(|item| item.position == Some(player_position))
+                .map(|item| item.entity)
+                .collect();
+            // Keep the order stable so menu letters don't shuffle
+            items_here.sort();
+
+            let item = match (choice, items_here.as_slice()) {
+                (Some(item), _) if items_here.contains(item) => *item,
+                (Some(_), _) => return,
+                (None, []) => {
+                    game_log.add_entry_srgb(
+                        LogCategory::Items,
+                        "There is nothing here to pick up.",
+                        Color::srgb(0.5, 0.5, 0.5),
+                    );
+                    return;
+                }
+                (None, [item]) => *item,
+                (None, _) => {
+                    // Let the player choose, picking up happens once they do
+                    pickup_choices.0 = items_here.clone();
+                    next_menu.set(MenuState::PickupItem);
+                    return;
+                }
+            };
+
+            if carried >= ModelConstants::BACKPACK_CAPACITY {
+                game_log.add_entry_srgb(
+                    LogCategory::Items,
+                    "Your backpack is full.",
+                    Color::srgb(1.0, 0.3, 0.3),
+                );
+                return;
+            }
+
+            let Ok(item) = item_query.get(item) else {
+                return;
+            };
+            commands
+                .entity(item.entity)
+                .remove::<(Position, MapLevel)>()
+                .insert(InBackpack {
+                    owner: player_entity,
+                });
+            game_log.add_entry_srgb(
+                LogCategory::Items,
+                format!("You pick up the {}.", item.name.0),
+                Color::srgb(0.0, 1.0, 0.0),
+            );
+        }
+        PlayerAction::UseItem(item, target) => {
+            let Ok(item) = item_query.get(*item) else {
+                return;
+            };
+            if !item.carried_by(player_entity) {
+                return;
+            }
+            if !item.consumable {
+                game_log.add_entry_srgb(
+                    LogCategory::Items,
+                    format!("You can't use the {}.", item.name.0),
+                    Color::srgb(1.0, 0.3, 0.3),
+                );
+                return;
+            }
+
+            // Ranged items need a target tile before they can be used
+            if let Some(ranged) = item.ranged {
+                let Some(target) = target else {
+                    *targeting = Targeting {
+                        item: Some(item.entity),
+                        range: ranged.range,
+                        cursor: *player_position,
+                    };
+                    next_menu.set(MenuState::Targeting);
+                    return;
+                };
+
+                let distance = DistanceAlg::Pythagoras.distance2d(
+                    Point::new(player_position.x, player_position.y),
+                    Point::new(target.x, target.y),
+                );
+                let visible = map.in_bounds(target.x, target.y)
+                    && map.visible_tiles[map.xy_idx(target.x, target.y)];
+                if distance > ranged.range as f32 || !visible {
+                    game_log.add_entry_srgb(
+                        LogCategory::Items,
+                        "That target is out of reach.",
+                        Color::srgb(1.0, 0.3, 0.3),
+                    );
+                    return;
+                }
+            }
+
+            game_log.add_entry_srgb(
+                LogCategory::Items,
+                format!("You use the {}.", item.name.0),
+                Color::srgb(0.0, 1.0, 0.0),
+            );
+            item_used_events.send(ItemUsed {
+                user: player_entity,
+                item: item.entity,
+                target: *target,
+            });
+        }
+        PlayerAction::DropItem(item) => {
+            let Ok(item) = item_query.get(*item) else {
+                return;
+            };
+            if !item.carried_by(player_entity) {
+                return;
+            }
+
+            commands
+                .entity(item.entity)
+                .remove::<InBackpack>()
+                .insert((*player_position, MapLevel(dungeon.depth)));
+            game_log.add_entry_srgb(
+                LogCategory::Items,
+                format!("You drop the {}.", item.name.0),
+                Color::srgb(0.5, 0.5, 0.5),
+            );
+        }
+        PlayerAction::EquipItem(item) => {
+            let Ok(item) = item_query.get(*item) else {
+                return;
+            };
+            let Some(equippable) = item.equippable else {
+                game_log.add_entry_srgb(
+                    LogCategory::Items,
+                    format!("You can't equip the {}.", item.name.0),
+                    Color::srgb(1.0, 0.3, 0.3),
+                );
+                return;
+            };
+            if !item.carried_by(player_entity) {
+                return;
+            }
+
+            // Only one item per slot, whatever was there goes back in the backpack
+            if let Some(previous) = item_query.iter().find(|other| {
+                other.equipped_by(player_entity)
+                    && other
+                        .equipped
+                        .is_some_and(|equipped| equipped.slot == equippable.slot)
+            }) {
+                commands
+                    .entity(previous.entity)
+                    .remove::<Equipped>()
+                    .insert(InBackpack {
+                        owner: player_entity,
+                    });
+                game_log.add_entry_srgb(
+                    LogCategory::Items,
+                    format!("You put away the {}.", previous.name.0),
+                    Color::srgb(0.5, 0.5, 0.5),
+                );
+            }
+
+            commands
+                .entity(item.entity)
+                .remove::<InBackpack>()
+                .insert(Equipped {
+                    owner: player_entity,
+                    slot: equippable.slot,
+                });
+            game_log.add_entry_srgb(
+                LogCategory::Items,
+                format!("You equip the {}.", item.name.0),
+                Color::srgb(0.0, 1.0, 0.0),
+            );
+        }
+        PlayerAction::UnequipItem(item) => {
+            let Ok(item) = item_query.get(*item) else {
+                return;
+            };
+            if !item.equipped_by(player_entity) {
+                return;
+            }
+            if carried >= ModelConstants::BACKPACK_CAPACITY {
+                game_log.add_entry_srgb(
+                    LogCategory::Items,
+                    format!("There is no room for the {}.", item.name.0),
+                    Color::srgb(1.0, 0.3, 0.3),
+                );
+                return;
+            }
+
+            commands
+                .entity(item.entity)
+                .remove::<Equipped>()
+                .insert(InBackpack {
+                    owner: player_entity,
+                });
+            game_log.add_entry_srgb(
+                LogCategory::Items,
+                format!("You unequip the {}.", item.name.0),
+                Color::srgb(0.5, 0.5, 0.5),
+            );
+        }
+        // Movement and waiting are handled by `handle_player_actions`
+        _ => return,
+    }
+
+    player_turn.spend(ModelConstants::ACTION_COST);
+}
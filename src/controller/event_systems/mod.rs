@@ -1,3 +1,5 @@
+mod item_action;
 mod player_action;
 
+pub use item_action::*;
 pub use player_action::*;
@@ -1,69 +1,134 @@
-use bevy::prelude::*;
-
 use crate::controller::events::PlayerAction;
-use crate::model::components::{FieldOfView, Player, Position};
-use crate::model::resources::{GameLog, Map, TurnState};
-use crate::model::systems::try_move_entity;
+use crate::model::components::{
+    FieldOfView, Player, Position, Stats, StatusEffects, StatusKind, TerrainType,
+};
+use crate::model::events::{AttackIntent, ChangeLevel};
+use crate::model::resources::{Dungeon, GameLog, GameRng, LogCategory, Map};
+use crate::model::systems::{try_move_entity, MoveDirection, MoveOutcome, PlayerTurn};
+use crate::model::ModelConstants;
+use bevy::prelude::*;
 
 pub fn handle_player_actions(
     player_action_trigger: Trigger<PlayerAction>,
-    player_query: Query<Entity, With<Player>>,
+    player_query: Query<(Entity, &StatusEffects), With<Player>>,
     map: Res<Map>,
+    dungeon: Res<Dungeon>,
+    mut rng: ResMut<GameRng>,
+    mut player_turn: PlayerTurn,
     mut game_log: ResMut<GameLog>,
-    mut turn_state: ResMut<TurnState>,
     mut position_query: Query<&mut Position>,
     mut fov_query: Query<&mut FieldOfView>,
+    actor_query: Query<Entity, With<Stats>>,
+    mut attack_events: EventWriter<AttackIntent>,
+    mut level_events: EventWriter<ChangeLevel>,
 ) {
-    let Ok(player_entity) = player_query.get_single() else {
+    let Ok((player_entity, effects)) = player_query.get_single() else {
         return;
     };
 
+    // Only act when it is actually the player's turn
+    if !player_turn.is_turn_of(player_entity) {
+        return;
+    }
+    rng.start_player_action(player_turn.turn_state());
+
+    let mut cost = ModelConstants::ACTION_COST;
+
     // You can access the trigger data via the `Observer`
     match player_action_trigger.event() {
         PlayerAction::Move(direction) => {
-            let moved = try_move_entity(
+            // Confused players stumble in a random direction
+            let direction = if effects.has(StatusKind::Confused) {
+                *rng.combat
+                    .random_slice_entry(&MoveDirection::ALL)
+                    .unwrap_or(direction)
+            } else {
+                *direction
+            };
+
+            match try_move_entity(
                 player_entity,
-                *direction,
+                direction,
                 &map,
                 &mut position_query,
                 &mut fov_query,
-            );
+                &actor_query,
+            ) {
+                MoveOutcome::Moved => {
+                    game_log.add_entry_srgb(
+                        LogCategory::Movement,
+                        "You move.",
+                        Color::srgb(0.5, 0.5, 1.0),
+                    );
 
-            if moved {
-                game_log.add_entry_srgb("You move.", Color::srgb(0.5, 0.5, 1.0));
-            } else {
-                game_log.add_entry_srgb("Something blocks your way.", Color::srgb(1.0, 0.3, 0.3));
+                    if let Ok(position) = position_query.get(player_entity) {
+                        match map.tiles[map.xy_idx(position.x, position.y)] {
+                            TerrainType::DownStairs => game_log.add_entry_srgb(
+                                LogCategory::Movement,
+                                "There is a staircase leading down here.",
+                                Color::srgb(1.0, 1.0, 1.0),
+                            ),
+                            TerrainType::UpStairs => game_log.add_entry_srgb(
+                                LogCategory::Movement,
+                                "There is a staircase leading up here.",
+                                Color::srgb(1.0, 1.0, 1.0),
+                            ),
+                            _ => {}
+                        }
+                    }
+                }
+                MoveOutcome::Attack(target) => {
+                    attack_events.send(AttackIntent {
+                        attacker: player_entity,
+                        target,
+                    });
+                }
+                MoveOutcome::Blocked => {
+                    game_log.add_entry_srgb(
+                        LogCategory::Movement,
+                        "Something blocks your way.",
+                        Color::srgb(1.0, 0.3, 0.3),
+                    );
+                    // Bumping into a wall is free
+                    cost = 0;
+                }
             }
         }
         PlayerAction::Wait => {
-            game_log.add_entry_srgb("You wait.", Color::srgb(0.5, 0.5, 1.0));
+            game_log.add_entry_srgb(
+                LogCategory::Movement,
+                "You wait.",
+                Color::srgb(0.5, 0.5, 1.0),
+            );
         }
-        PlayerAction::PickupItem => {
-            // Get player position
-            if let Ok(_player_pos) = position_query.get(player_entity) {
-                // Find items at player position
-                // This would be implemented in a more complete game
-                game_log.add_entry_srgb("You pick up an item.", Color::srgb(0.0, 1.0, 0.0));
+        PlayerAction::Descend | PlayerAction::Ascend => {
+            let (stairs, depth) = match player_action_trigger.event() {
+                PlayerAction::Descend => (TerrainType::DownStairs, dungeon.depth + 1),
+                _ => (TerrainType::UpStairs, dungeon.depth - 1),
+            };
+
+            let Ok(position) = position_query.get(player_entity) else {
+                return;
+            };
+            if map.tiles[map.xy_idx(position.x, position.y)] != stairs {
+                let direction = if stairs == TerrainType::DownStairs {
+                    "down"
+                } else {
+                    "up"
+                };
+                game_log.add_entry_srgb(
+                    LogCategory::Movement,
+                    format!("There is no way {direction} here."),
+                    Color::srgb(0.5, 0.5, 0.5),
+                );
+                return;
             }
+
+            level_events.send(ChangeLevel { depth });
         }
-        PlayerAction::UseItem(_item_entity) => {
-            // Use item logic would go here
-            game_log.add_entry_srgb("You use an item.", Color::srgb(0.0, 1.0, 0.0));
-        }
-        PlayerAction::DropItem(_item_entity) => {
-            // Drop item logic would go here
-            game_log.add_entry_srgb("You drop an item.", Color::srgb(0.5, 0.5, 0.5));
-        }
-        PlayerAction::EquipItem(_item_entity) => {
-            // Equip item logic would go here
-            game_log.add_entry_srgb("You equip an item.", Color::srgb(0.0, 1.0, 0.0));
-        }
-        PlayerAction::UnequipItem(_item_entity) => {
-            // Unequip item logic would go here
-            game_log.add_entry_srgb("You unequip an item.", Color::srgb(0.5, 0.5, 0.5));
-        }
+        // Item actions are handled by `handle_item_actions`
+        _ => return,
     }
 
-    // After player action, update turn state
-    turn_state.next_turn();
+    player_turn.spend(cost);
 }
@@ -1,13 +1,20 @@
 use bevy::prelude::*;
 
+use crate::model::components::Position;
 use crate::model::systems::MoveDirection;
 
 #[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PlayerAction {
     Move(MoveDirection),
     Wait,
-    PickupItem,
-    UseItem(Entity),
+    /// Take the stairs down from the player's tile
+    Descend,
+    /// Take the stairs up from the player's tile
+    Ascend,
+    /// Pick up a specific item, or with `None` whatever lies on the player's tile
+    PickupItem(Option<Entity>),
+    /// Use an item, aimed at a tile when it is ranged. Without a target one is asked for.
+    UseItem(Entity, Option<Position>),
     DropItem(Entity),
     EquipItem(Entity),
     UnequipItem(Entity),
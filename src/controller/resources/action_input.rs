@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use super::InputAction;
+
+/// Actions triggered this frame, whatever device they came from
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ActionInput {
+    just_pressed: HashSet<InputAction>,
+}
+
+impl ActionInput {
+    pub fn press(&mut self, action: InputAction) {
+        self.just_pressed.insert(action);
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// Whether anything at all was pressed this frame
+    pub fn any(&self) -> bool {
+        !self.just_pressed.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.just_pressed.clear();
+    }
+}
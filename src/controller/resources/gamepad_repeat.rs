@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::model::systems::MoveDirection;
+
+/// Repeats the held d-pad or stick direction like a held key
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GamepadRepeat {
+    pub direction: Option<MoveDirection>,
+    pub timer: Timer,
+}
+
+impl GamepadRepeat {
+    /// Pause before a held direction starts repeating
+    pub const DELAY: f32 = 0.35;
+    /// Time between repeats after that
+    pub const RATE: f32 = 0.12;
+    /// How far the stick has to be pushed to count
+    pub const DEADZONE: f32 = 0.5;
+
+    /// Whether `direction` should fire this frame
+    pub fn update(&mut self, direction: Option<MoveDirection>, delta: std::time::Duration) -> bool {
+        if direction != self.direction {
+            self.direction = direction;
+            self.timer = Timer::from_seconds(Self::DELAY, TimerMode::Once);
+            return direction.is_some();
+        }
+
+        if direction.is_none() || !self.timer.tick(delta).just_finished() {
+            return false;
+        }
+        self.timer = Timer::from_seconds(Self::RATE, TimerMode::Once);
+        true
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+/// The backpack item the inventory screen shows details and actions for
+#[derive(Resource, Debug, Clone, Default)]
+pub struct InventoryMenu {
+    pub selected: Option<Entity>,
+}
+
+/// What can be done with the selected item, in the order the detail pane lists them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemCommand {
+    Use,
+    Equip,
+    Drop,
+}
+
+impl ItemCommand {
+    /// The commands that make sense for an item
+    pub fn available(consumable: bool, equippable: bool) -> Vec<ItemCommand> {
+        let mut commands = Vec::new();
+        if consumable {
+            commands.push(ItemCommand::Use);
+        }
+        if equippable {
+            commands.push(ItemCommand::Equip);
+        }
+        commands.push(ItemCommand::Drop);
+        commands
+    }
+
+    pub const fn key(&self) -> KeyCode {
+        match self {
+            ItemCommand::Use => KeyCode::KeyU,
+            ItemCommand::Equip => KeyCode::KeyE,
+            ItemCommand::Drop => KeyCode::KeyD,
+        }
+    }
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            ItemCommand::Use => "(u) Use",
+            ItemCommand::Equip => "(e) Equip",
+            ItemCommand::Drop => "(d) Drop",
+        }
+    }
+}
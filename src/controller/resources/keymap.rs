@@ -0,0 +1,723 @@
+use bevy::prelude::*;
+use serde::{de::IntoDeserializer, Deserialize};
+use std::{collections::HashMap, fmt, fs, io, path::PathBuf, str::FromStr};
+
+/// Everything a key can be bound to, both in play and in menus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputAction {
+    MoveNorth,
+    MoveSouth,
+    MoveEast,
+    MoveWest,
+    MoveNorthEast,
+    MoveNorthWest,
+    MoveSouthEast,
+    MoveSouthWest,
+    Wait,
+    PickUp,
+    Descend,
+    Ascend,
+    OpenInventory,
+    OpenEquipment,
+    OpenMessageLog,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    Confirm,
+    Cancel,
+    NewGame,
+    Continue,
+    Options,
+    Quit,
+}
+
+impl InputAction {
+    /// The name used for the action in the keymap file
+    pub const fn name(&self) -> &'static str {
+        match self {
+            InputAction::MoveNorth => "move_north",
+            InputAction::MoveSouth => "move_south",
+            InputAction::MoveEast => "move_east",
+            InputAction::MoveWest => "move_west",
+            InputAction::MoveNorthEast => "move_north_east",
+            InputAction::MoveNorthWest => "move_north_west",
+            InputAction::MoveSouthEast => "move_south_east",
+            InputAction::MoveSouthWest => "move_south_west",
+            InputAction::Wait => "wait",
+            InputAction::PickUp => "pick_up",
+            InputAction::Descend => "descend",
+            InputAction::Ascend => "ascend",
+            InputAction::OpenInventory => "open_inventory",
+            InputAction::OpenEquipment => "open_equipment",
+            InputAction::OpenMessageLog => "open_message_log",
+            InputAction::MenuUp => "menu_up",
+            InputAction::MenuDown => "menu_down",
+            InputAction::MenuLeft => "menu_left",
+            InputAction::MenuRight => "menu_right",
+            InputAction::Confirm => "confirm",
+            InputAction::Cancel => "cancel",
+            InputAction::NewGame => "new_game",
+            InputAction::Continue => "continue",
+            InputAction::Options => "options",
+            InputAction::Quit => "quit",
+        }
+    }
+}
+
+/// A screen's input system, one key may only do one thing on each
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputScreen {
+    Gameplay,
+    Targeting,
+    LogHistory,
+    StartMenu,
+    /// Pause, options, game over and the pick-up and item menus
+    Menu,
+    Inventory,
+    Equipment,
+}
+
+impl InputScreen {
+    pub const ALL: [InputScreen; 7] = [
+        InputScreen::Gameplay,
+        InputScreen::Targeting,
+        InputScreen::LogHistory,
+        InputScreen::StartMenu,
+        InputScreen::Menu,
+        InputScreen::Inventory,
+        InputScreen::Equipment,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            InputScreen::Gameplay => "gameplay",
+            InputScreen::Targeting => "targeting",
+            InputScreen::LogHistory => "message log",
+            InputScreen::StartMenu => "start menu",
+            InputScreen::Menu => "menus",
+            InputScreen::Inventory => "inventory",
+            InputScreen::Equipment => "equipment",
+        }
+    }
+
+    /// What the screen reacts to, the actions in one group do the same thing there
+    pub fn controls(&self) -> &'static [&'static [InputAction]] {
+        use InputAction::*;
+
+        match self {
+            InputScreen::Gameplay => &[
+                &[MoveNorth],
+                &[MoveSouth],
+                &[MoveEast],
+                &[MoveWest],
+                &[MoveNorthEast],
+                &[MoveNorthWest],
+                &[MoveSouthEast],
+                &[MoveSouthWest],
+                &[Wait],
+                &[PickUp],
+                &[Descend],
+                &[Ascend],
+                &[OpenInventory],
+                &[OpenEquipment],
+                &[OpenMessageLog],
+                &[Cancel],
+            ],
+            InputScreen::Targeting => &[
+                &[MoveNorth],
+                &[MoveSouth],
+                &[MoveEast],
+                &[MoveWest],
+                &[MoveNorthEast],
+                &[MoveNorthWest],
+                &[MoveSouthEast],
+                &[MoveSouthWest],
+                &[Confirm],
+                &[Cancel],
+            ],
+            InputScreen::LogHistory => &[
+                &[MoveNorth, MenuUp],
+                &[MoveSouth, MenuDown],
+                &[Cancel, OpenMessageLog],
+            ],
+            InputScreen::StartMenu => &[
+                &[MenuUp],
+                &[MenuDown],
+                &[Confirm],
+                &[NewGame],
+                &[Continue],
+                &[Options],
+                &[Quit],
+            ],
+            InputScreen::Menu => &[
+                &[MenuUp],
+                &[MenuDown],
+                &[MenuLeft],
+                &[MenuRight],
+                &[Confirm],
+                &[Cancel],
+            ],
+            InputScreen::Inventory => &[
+                &[MenuUp],
+                &[MenuDown],
+                &[Confirm],
+                &[Cancel],
+                &[OpenInventory],
+            ],
+            InputScreen::Equipment => &[
+                &[MenuUp],
+                &[MenuDown],
+                &[Confirm],
+                &[Cancel],
+                &[OpenEquipment],
+            ],
+        }
+    }
+
+    /// Which of `controls` the action belongs to, if the screen reads it at all
+    fn control(&self, action: InputAction) -> Option<usize> {
+        self.controls()
+            .iter()
+            .position(|group| group.contains(&action))
+    }
+}
+
+/// A key together with the modifiers that must be held for it, e.g. `Shift+Period`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyChord {
+    pub const fn key(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    pub const fn shift(key: KeyCode) -> Self {
+        Self {
+            shift: true,
+            ..Self::key(key)
+        }
+    }
+
+    /// Pressed this frame with exactly these modifiers held, so `Period` and
+    /// `Shift+Period` can do different things
+    pub fn just_pressed(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        keyboard.just_pressed(self.key)
+            && self.ctrl == keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+            && self.shift == keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+            && self.alt == keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+    }
+
+    /// Short name for on-screen hints, e.g. `SHIFT+PERIOD` or `W`
+    pub fn label(&self) -> String {
+        let name = format!("{:?}", self.key);
+        let name = ["Key", "Digit", "Arrow"]
+            .iter()
+            .find_map(|prefix| name.strip_prefix(prefix))
+            .unwrap_or(name.as_str());
+
+        let mut label = String::new();
+        for (held, modifier) in [
+            (self.ctrl, "CTRL+"),
+            (self.shift, "SHIFT+"),
+            (self.alt, "ALT+"),
+        ] {
+            if held {
+                label.push_str(modifier);
+            }
+        }
+        label.push_str(&name.to_ascii_uppercase());
+        label
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    /// Parses `Ctrl+Shift+KeyS` style chords, keys use bevy's `KeyCode` names
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let Some(key) = parts.pop() else {
+            return Err(format!("'{text}' has no key"));
+        };
+
+        let key = KeyCode::deserialize(
+            IntoDeserializer::<serde::de::value::Error>::into_deserializer(key),
+        )
+        .map_err(|_| format!("'{key}' is not a key name"))?;
+        let mut chord = KeyChord::key(key);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "alt" => chord.alt = true,
+                _ => return Err(format!("'{modifier}' is not a modifier")),
+            }
+        }
+
+        Ok(chord)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        write!(f, "{:?}", self.key)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapPreset {
+    #[default]
+    Wasd,
+    Arrows,
+    Numpad,
+    Vi,
+}
+
+/// One chord doing more than one thing on a screen, with the first action of each
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeymapConflict {
+    pub screen: InputScreen,
+    pub chord: KeyChord,
+    pub actions: Vec<InputAction>,
+}
+
+impl fmt::Display for KeymapConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let actions: Vec<&str> = self.actions.iter().map(InputAction::name).collect();
+        write!(
+            f,
+            "{} is bound to {} on the {} screen, only {} will be used",
+            self.chord,
+            actions.join(" and "),
+            self.screen.name(),
+            actions[0]
+        )
+    }
+}
+
+/// The keymap file: a preset plus per-action overrides
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct KeymapFile {
+    preset: KeymapPreset,
+    bindings: HashMap<InputAction, Vec<String>>,
+}
+
+/// Which chords and gamepad buttons trigger which `InputAction`
+#[derive(Resource, Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(InputAction, KeyChord)>,
+    /// A pad has few buttons, so they double up between play and menus
+    buttons: Vec<(InputAction, GamepadButton)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(KeymapPreset::default())
+    }
+}
+
+impl Keymap {
+    const FILE_NAME: &str = "keymap";
+    const PATH: &str = "settings";
+
+    fn file_path() -> PathBuf {
+        let mut file_path = PathBuf::from(Self::PATH);
+        file_path.push(Self::FILE_NAME);
+        file_path.set_extension("toml");
+        file_path
+    }
+
+    pub fn preset(preset: KeymapPreset) -> Self {
+        use InputAction::*;
+        use KeyCode::*;
+
+        let (north, south, east, west, wait) = match preset {
+            KeymapPreset::Wasd => (KeyW, KeyS, KeyD, KeyA, Space),
+            KeymapPreset::Arrows => (ArrowUp, ArrowDown, ArrowRight, ArrowLeft, Space),
+            KeymapPreset::Numpad => (Numpad8, Numpad2, Numpad6, Numpad4, Numpad5),
+            KeymapPreset::Vi => (KeyK, KeyJ, KeyL, KeyH, Period),
+        };
+
+        // Diagonals live on the numpad corners, vi-keys use `yubn` instead
+        let (north_east, north_west, south_east, south_west) = match preset {
+            KeymapPreset::Vi => (KeyU, KeyY, KeyN, KeyB),
+            _ => (Numpad9, Numpad7, Numpad3, Numpad1),
+        };
+
+        // vi-keys wait on `.` so the stairs move to `>` and `<`
+        let (descend, ascend) = match preset {
+            KeymapPreset::Vi => (KeyChord::shift(Period), KeyChord::shift(Comma)),
+            _ => (KeyChord::key(Period), KeyChord::key(Comma)),
+        };
+
+        let mut bindings = vec![
+            (MoveNorth, KeyChord::key(north)),
+            (MoveSouth, KeyChord::key(south)),
+            (MoveEast, KeyChord::key(east)),
+            (MoveWest, KeyChord::key(west)),
+            (MoveNorthEast, KeyChord::key(north_east)),
+            (MoveNorthWest, KeyChord::key(north_west)),
+            (MoveSouthEast, KeyChord::key(south_east)),
+            (MoveSouthWest, KeyChord::key(south_west)),
+            (Wait, KeyChord::key(wait)),
+            (PickUp, KeyChord::key(KeyG)),
+            (Descend, descend),
+            (Ascend, ascend),
+            (OpenInventory, KeyChord::key(KeyI)),
+            (OpenEquipment, KeyChord::key(KeyE)),
+            (OpenMessageLog, KeyChord::key(KeyM)),
+            (MenuUp, KeyChord::key(ArrowUp)),
+            (MenuDown, KeyChord::key(ArrowDown)),
+            (MenuLeft, KeyChord::key(ArrowLeft)),
+            (MenuRight, KeyChord::key(ArrowRight)),
+            (Confirm, KeyChord::key(Enter)),
+            (Cancel, KeyChord::key(Escape)),
+            (NewGame, KeyChord::key(KeyN)),
+            (Continue, KeyChord::key(KeyC)),
+            (Options, KeyChord::key(KeyO)),
+            (Quit, KeyChord::key(KeyQ)),
+        ];
+        if preset == KeymapPreset::Numpad {
+            bindings.push((Confirm, KeyChord::key(NumpadEnter)));
+        }
+
+        Self {
+            bindings,
+            buttons: Self::gamepad_buttons(),
+        }
+    }
+
+    /// Face buttons act and confirm, shoulders take the stairs, the d-pad is handled separately
+    fn gamepad_buttons() -> Vec<(InputAction, GamepadButton)> {
+        use GamepadButton::*;
+        use InputAction::*;
+
+        vec![
+            (Wait, South),
+            (Confirm, South),
+            (Cancel, East),
+            (Cancel, Start),
+            (PickUp, West),
+            (OpenInventory, North),
+            (OpenEquipment, RightTrigger2),
+            (OpenMessageLog, Select),
+            (Ascend, LeftTrigger),
+            (Descend, RightTrigger),
+        ]
+    }
+
+    /// Loads `settings/keymap.toml`, falling back to the WASD preset when there is none.
+    ///
+    /// Anything wrong with the file is logged as a warning and otherwise ignored.
+    pub fn load() -> Self {
+        let contents = match fs::read_to_string(Self::file_path()) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                warn!("Failed to read keymap, using defaults: {err}");
+                return Self::default();
+            }
+        };
+
+        match Self::from_toml(&contents) {
+            Ok((mut keymap, problems)) => {
+                for problem in problems {
+                    warn!("Keymap: {problem}");
+                }
+                for conflict in keymap.conflicts() {
+                    warn!("Keymap: {conflict}");
+                }
+                keymap.resolve_conflicts();
+                keymap
+            }
+            Err(err) => {
+                warn!("Failed to parse keymap, using defaults: {err}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Builds a keymap from the file contents, along with any bindings that were skipped
+    pub fn from_toml(contents: &str) -> Result<(Self, Vec<String>), toml::de::Error> {
+        let file: KeymapFile = toml::from_str(contents)?;
+        let mut keymap = Self::preset(file.preset);
+        let mut problems = Vec::new();
+
+        // Sorted so conflicts resolve the same way every time
+        let mut overrides: Vec<_> = file.bindings.into_iter().collect();
+        overrides.sort_by_key(|(action, _)| *action);
+
+        for (action, chords) in overrides {
+            keymap.bindings.retain(|(bound, _)| *bound != action);
+            for chord in chords {
+                match chord.parse::<KeyChord>() {
+                    Ok(chord) => keymap.bindings.push((action, chord)),
+                    Err(err) => problems.push(format!("skipping {} binding, {err}", action.name())),
+                }
+            }
+        }
+        keymap.bindings.sort_by_key(|(action, _)| *action);
+
+        Ok((keymap, problems))
+    }
+
+    /// Chords that do more than one thing on some screen, see `InputScreen::controls`
+    pub fn conflicts(&self) -> Vec<KeymapConflict> {
+        let mut conflicts = Vec::new();
+        for screen in InputScreen::ALL {
+            let mut by_chord: Vec<(KeymapConflict, Vec<usize>)> = Vec::new();
+            for (action, chord) in &self.bindings {
+                let Some(control) = screen.control(*action) else {
+                    continue;
+                };
+                match by_chord
+                    .iter_mut()
+                    .find(|(conflict, _)| conflict.chord == *chord)
+                {
+                    Some((conflict, controls)) if !controls.contains(&control) => {
+                        conflict.actions.push(*action);
+                        controls.push(control);
+                    }
+                    Some(_) => {}
+                    None => by_chord.push((
+                        KeymapConflict {
+                            screen,
+                            chord: *chord,
+                            actions: vec![*action],
+                        },
+                        vec![control],
+                    )),
+                }
+            }
+
+            conflicts.extend(
+                by_chord
+                    .into_iter()
+                    .map(|(conflict, _)| conflict)
+                    .filter(|conflict| conflict.actions.len() > 1),
+            );
+        }
+
+        conflicts
+    }
+
+    /// Unbinds conflicting chords from all but the first action, one at a time since
+    /// unbinding a chord can settle its conflicts on other screens too
+    fn resolve_conflicts(&mut self) {
+        while let Some(conflict) = self.conflicts().into_iter().next() {
+            let unbound = (conflict.actions[1], conflict.chord);
+            self.bindings.retain(|binding| *binding != unbound);
+        }
+    }
+
+    /// Chords bound to `action`
+    pub fn chords(&self, action: InputAction) -> impl Iterator<Item = &KeyChord> {
+        self.bindings
+            .iter()
+            .filter(move |(bound, _)| *bound == action)
+            .map(|(_, chord)| chord)
+    }
+
+    /// The first chord bound to `action` as hints show it, `-` when there is none
+    pub fn hint(&self, action: InputAction) -> String {
+        self.chords(action)
+            .next()
+            .map_or_else(|| "-".to_string(), KeyChord::label)
+    }
+
+    /// Actions whose chords were pressed this frame
+    pub fn just_pressed<'a>(
+        &'a self,
+        keyboard: &'a ButtonInput<KeyCode>,
+    ) -> impl Iterator<Item = InputAction> + 'a {
+        self.bindings
+            .iter()
+            .filter(|(_, chord)| chord.just_pressed(keyboard))
+            .map(|(action, _)| *action)
+    }
+
+    /// Actions whose gamepad buttons were pressed this frame
+    pub fn gamepad_just_pressed<'a>(
+        &'a self,
+        gamepad: &'a Gamepad,
+    ) -> impl Iterator<Item = InputAction> + 'a {
+        self.buttons
+            .iter()
+            .filter(|(_, button)| gamepad.just_pressed(*button))
+            .map(|(action, _)| *action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chords_round_trip_through_display() {
+        for text in [
+            "KeyW",
+            "Shift+Period",
+            "Ctrl+Shift+KeyS",
+            "Ctrl+Alt+Numpad5",
+        ] {
+            let chord: KeyChord = text.parse().unwrap();
+            assert_eq!(chord.to_string(), text);
+            assert_eq!(chord.to_string().parse::<KeyChord>(), Ok(chord));
+        }
+    }
+
+    #[test]
+    fn chord_modifiers_are_case_insensitive_and_normalised() {
+        let chord: KeyChord = "shift + control+KeyS".parse().unwrap();
+        assert_eq!(
+            chord,
+            KeyChord {
+                key: KeyCode::KeyS,
+                ctrl: true,
+                shift: true,
+                alt: false,
+            }
+        );
+        assert_eq!(chord.to_string(), "Ctrl+Shift+KeyS");
+    }
+
+    #[test]
+    fn bad_chords_are_rejected() {
+        assert!("".parse::<KeyChord>().is_err());
+        assert!("NotAKey".parse::<KeyChord>().is_err());
+        assert!("Hyper+KeyA".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn from_toml_rejects_malformed_files() {
+        assert!(Keymap::from_toml("preset = ").is_err());
+        assert!(Keymap::from_toml("preset = \"dvorak\"").is_err());
+        assert!(Keymap::from_toml("[bindings]\nfly = [\"KeyF\"]").is_err());
+    }
+
+    #[test]
+    fn from_toml_skips_bad_chords() {
+        let (keymap, problems) =
+            Keymap::from_toml("[bindings]\nwait = [\"Hyper+Space\", \"Numpad5\"]").unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(
+            keymap.chords(InputAction::Wait).collect::<Vec<_>>(),
+            [&KeyChord::key(KeyCode::Numpad5)]
+        );
+    }
+
+    #[test]
+    fn from_toml_overrides_the_preset() {
+        let (keymap, problems) =
+            Keymap::from_toml("preset = \"vi\"\n[bindings]\nwait = [\"KeyZ\"]").unwrap();
+        assert!(problems.is_empty());
+        assert_eq!(keymap.hint(InputAction::MoveNorth), "K");
+        assert_eq!(keymap.hint(InputAction::Descend), "SHIFT+PERIOD");
+        assert_eq!(keymap.hint(InputAction::Wait), "Z");
+    }
+
+    #[test]
+    fn presets_have_no_conflicts() {
+        for preset in [
+            KeymapPreset::Wasd,
+            KeymapPreset::Arrows,
+            KeymapPreset::Numpad,
+            KeymapPreset::Vi,
+        ] {
+            assert!(Keymap::preset(preset).conflicts().is_empty(), "{preset:?}");
+        }
+    }
+
+    fn conflicts_in(toml: &str) -> Vec<(InputScreen, Vec<InputAction>)> {
+        let (keymap, _) = Keymap::from_toml(toml).unwrap();
+        keymap
+            .conflicts()
+            .into_iter()
+            .map(|conflict| (conflict.screen, conflict.actions))
+            .collect()
+    }
+
+    #[test]
+    fn gameplay_conflicts_include_cancel() {
+        // Space is `wait` in the WASD preset, and gameplay reads cancel to pause
+        assert_eq!(
+            conflicts_in("[bindings]\ncancel = [\"Space\"]"),
+            [(
+                InputScreen::Gameplay,
+                vec![InputAction::Wait, InputAction::Cancel]
+            )]
+        );
+        assert_eq!(
+            conflicts_in("[bindings]\nwait = [\"KeyG\"]"),
+            [(
+                InputScreen::Gameplay,
+                vec![InputAction::Wait, InputAction::PickUp]
+            )]
+        );
+    }
+
+    #[test]
+    fn targeting_conflicts_include_moves() {
+        // W moves north in the WASD preset, targeting moves the cursor with it
+        assert_eq!(
+            conflicts_in("[bindings]\nconfirm = [\"KeyW\"]"),
+            [(
+                InputScreen::Targeting,
+                vec![InputAction::MoveNorth, InputAction::Confirm]
+            )]
+        );
+    }
+
+    #[test]
+    fn log_history_scroll_actions_may_share_a_key() {
+        // The arrows preset binds both scroll actions to the same arrows
+        assert!(conflicts_in("preset = \"arrows\"").is_empty());
+        assert_eq!(
+            conflicts_in("[bindings]\nmenu_up = [\"KeyS\"]"),
+            [(
+                InputScreen::LogHistory,
+                vec![InputAction::MoveSouth, InputAction::MenuUp]
+            )]
+        );
+    }
+
+    #[test]
+    fn actions_on_different_screens_may_share_a_key() {
+        // Wait and confirm are never read by the same screen
+        assert!(conflicts_in("[bindings]\nconfirm = [\"Space\"]").is_empty());
+        assert!(conflicts_in("[bindings]\nnew_game = [\"KeyG\"]").is_empty());
+    }
+
+    #[test]
+    fn resolving_conflicts_keeps_the_first_action() {
+        let (mut keymap, _) = Keymap::from_toml("[bindings]\nwait = [\"KeyG\"]").unwrap();
+        keymap.resolve_conflicts();
+
+        assert!(keymap.conflicts().is_empty());
+        assert_eq!(keymap.hint(InputAction::Wait), "G");
+        assert_eq!(keymap.chords(InputAction::PickUp).count(), 0);
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+use crate::model::resources::LogCategory;
+
+/// Where the message history screen is scrolled to and what it shows
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LogHistory {
+    /// Lines scrolled back from the newest entry
+    pub scroll: usize,
+    pub hidden: Vec<LogCategory>,
+}
+
+impl LogHistory {
+    /// Lines shown at once on a console `view_height` tall, and how far a page scrolls
+    pub fn page(view_height: i32) -> usize {
+        (view_height - 6).max(1) as usize
+    }
+
+    /// Categories currently shown, in display order
+    pub fn shown(&self) -> Vec<LogCategory> {
+        LogCategory::ALL
+            .into_iter()
+            .filter(|category| !self.hidden.contains(category))
+            .collect()
+    }
+
+    pub fn toggle(&mut self, category: LogCategory) {
+        if let Some(i) = self.hidden.iter().position(|hidden| *hidden == category) {
+            self.hidden.remove(i);
+        } else {
+            self.hidden.push(category);
+        }
+        self.scroll = 0;
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use super::{ActionInput, InputAction};
+
+/// Highlighted row of the open list menu, so menus work without letter keys
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MenuCursor {
+    pub index: usize,
+}
+
+impl MenuCursor {
+    /// Steps with `MenuUp` and `MenuDown`, wrapping around a list of `len` rows
+    pub fn navigate(&mut self, actions: &ActionInput, len: usize) {
+        if len == 0 {
+            self.index = 0;
+            return;
+        }
+
+        if actions.just_pressed(InputAction::MenuUp) {
+            self.index = (self.index + len - 1) % len;
+        }
+        if actions.just_pressed(InputAction::MenuDown) {
+            self.index = (self.index + 1) % len;
+        }
+        self.index = self.index.min(len - 1);
+    }
+}
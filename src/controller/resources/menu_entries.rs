@@ -0,0 +1,122 @@
+use super::InputAction;
+use crate::AppSettings;
+
+/// Rows of the main menu, top to bottom
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MainMenuEntry {
+    NewGame,
+    Continue,
+    Options,
+    Quit,
+}
+
+impl MainMenuEntry {
+    pub const ALL: [MainMenuEntry; 4] = [
+        MainMenuEntry::NewGame,
+        MainMenuEntry::Continue,
+        MainMenuEntry::Options,
+        MainMenuEntry::Quit,
+    ];
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            MainMenuEntry::NewGame => "New game",
+            MainMenuEntry::Continue => "Continue",
+            MainMenuEntry::Options => "Options",
+            MainMenuEntry::Quit => "Quit",
+        }
+    }
+
+    /// The shortcut that picks this entry straight away
+    pub const fn action(&self) -> InputAction {
+        match self {
+            MainMenuEntry::NewGame => InputAction::NewGame,
+            MainMenuEntry::Continue => InputAction::Continue,
+            MainMenuEntry::Options => InputAction::Options,
+            MainMenuEntry::Quit => InputAction::Quit,
+        }
+    }
+}
+
+/// Rows of the pause screen, top to bottom
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseEntry {
+    Resume,
+    Options,
+    SaveAndQuit,
+}
+
+impl PauseEntry {
+    pub const ALL: [PauseEntry; 3] = [
+        PauseEntry::Resume,
+        PauseEntry::Options,
+        PauseEntry::SaveAndQuit,
+    ];
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            PauseEntry::Resume => "Resume",
+            PauseEntry::Options => "Options",
+            PauseEntry::SaveAndQuit => "Save and return to menu",
+        }
+    }
+}
+
+/// Rows of the options screen, top to bottom
+///
+/// The view size sizes the consoles at startup, so it is only edited in `app_settings.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionsEntry {
+    Fullscreen,
+    TileSize,
+    Back,
+}
+
+impl OptionsEntry {
+    pub const ALL: [OptionsEntry; 3] = [
+        OptionsEntry::Fullscreen,
+        OptionsEntry::TileSize,
+        OptionsEntry::Back,
+    ];
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            OptionsEntry::Fullscreen => "Fullscreen",
+            OptionsEntry::TileSize => "Tile size",
+            OptionsEntry::Back => "Back",
+        }
+    }
+
+    /// The current value as shown next to the label
+    pub fn value(&self, settings: &AppSettings) -> String {
+        match self {
+            OptionsEntry::Fullscreen => if settings.fullscreen { "On" } else { "Off" }.to_string(),
+            OptionsEntry::TileSize => format!("{} px", settings.tile_size),
+            OptionsEntry::Back => String::new(),
+        }
+    }
+
+    /// Moves the value one step up or down, returns whether anything changed
+    pub fn adjust(&self, settings: &mut AppSettings, up: bool) -> bool {
+        fn step(value: &mut u32, by: u32, min: u32, max: u32, up: bool) -> bool {
+            let stepped = if up {
+                value.saturating_add(by)
+            } else {
+                value.saturating_sub(by)
+            }
+            .clamp(min, max);
+            let changed = *value != stepped;
+            *value = stepped;
+            changed
+        }
+
+        match self {
+            OptionsEntry::Fullscreen => {
+                settings.fullscreen = !settings.fullscreen;
+                true
+            }
+            OptionsEntry::TileSize => step(&mut settings.tile_size, 2, 8, 32, up),
+            OptionsEntry::Back => false,
+        }
+    }
+}
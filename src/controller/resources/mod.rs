@@ -1 +1,23 @@
+mod action_input;
+mod gamepad_repeat;
+mod inventory_menu;
+mod keymap;
+mod log_history;
+mod menu_cursor;
+mod menu_entries;
+mod mouse_hover;
+mod pickup_choices;
+mod targeting;
+mod travel;
 
+pub use action_input::*;
+pub use gamepad_repeat::*;
+pub use inventory_menu::*;
+pub use keymap::*;
+pub use log_history::*;
+pub use menu_cursor::*;
+pub use menu_entries::*;
+pub use mouse_hover::*;
+pub use pickup_choices::*;
+pub use targeting::*;
+pub use travel::*;
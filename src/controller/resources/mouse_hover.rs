@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+use crate::model::components::Position;
+
+/// The revealed map tile under the mouse and the route the player would walk to reach it
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MouseHover {
+    pub tile: Option<Position>,
+    /// Where the player stood when `path` was found
+    pub from: Option<Position>,
+    /// Steps from the player to `tile`, not including the player's own tile
+    pub path: Vec<Position>,
+}
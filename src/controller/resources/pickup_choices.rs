@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Items on the player's tile offered by the pickup menu, in hotkey order
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PickupChoices(pub Vec<Entity>);
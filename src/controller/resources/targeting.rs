@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use crate::model::components::Position;
+
+/// A ranged item waiting for the player to pick a target tile
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Targeting {
+    pub item: Option<Entity>,
+    pub range: i32,
+    pub cursor: Position,
+}
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use crate::model::components::Position;
+
+/// Steps left of a click-to-travel walk, taken one per player turn
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Travel {
+    pub path: VecDeque<Position>,
+}
+
+impl Travel {
+    pub fn is_active(&self) -> bool {
+        !self.path.is_empty()
+    }
+
+    pub fn stop(&mut self) {
+        self.path.clear();
+    }
+}
@@ -1,30 +1,525 @@
 use bevy::prelude::*;
 
 use crate::controller::events::PlayerAction;
+use crate::controller::resources::{
+    ActionInput, GamepadRepeat, InputAction, InventoryMenu, ItemCommand, Keymap, LogHistory,
+    MainMenuEntry, MenuCursor, OptionsEntry, PauseEntry, PickupChoices, Targeting,
+};
+use crate::model::commands::{LoadGame, SaveGame};
+use crate::model::components::{
+    Consumable, EquipmentSlot, Equippable, Equipped, InBackpack, Player,
+};
+use crate::model::resources::{GameLog, LogCategory};
 use crate::model::systems::MoveDirection;
+use crate::view::resources::MapCamera;
+use crate::{AppSettings, MenuState, OptionsState, RunningState};
 
-pub fn keyboard_input(mut commands: Commands, keyboard: Res<ButtonInput<KeyCode>>) {
-    // Movement
-    if keyboard.just_pressed(KeyCode::KeyW) {
-        commands.trigger(PlayerAction::Move(MoveDirection::North));
+/// Menu hotkeys, in the order items are listed
+const LETTER_KEYS: [KeyCode; 26] = [
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+];
+
+/// Index of the letter pressed this frame, `a` being 0
+pub fn letter_pressed(keyboard: &ButtonInput<KeyCode>) -> Option<usize> {
+    LETTER_KEYS
+        .iter()
+        .position(|key| keyboard.just_pressed(*key))
+}
+
+/// Turns this frame's key presses into `InputAction`s through the `Keymap`.
+pub fn read_keyboard(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keymap: Res<Keymap>,
+    mut actions: ResMut<ActionInput>,
+) {
+    actions.clear();
+    for action in keymap.just_pressed(&keyboard) {
+        actions.press(action);
+    }
+}
+
+/// Adds the actions of every connected gamepad to this frame's `ActionInput`.
+///
+/// The d-pad and left stick repeat while held, like a key would.
+pub fn read_gamepad(
+    time: Res<Time>,
+    keymap: Res<Keymap>,
+    mut repeat: ResMut<GamepadRepeat>,
+    mut actions: ResMut<ActionInput>,
+    gamepad_query: Query<&Gamepad>,
+) {
+    let mut held = Vec2::ZERO;
+    for gamepad in gamepad_query.iter() {
+        for action in keymap.gamepad_just_pressed(gamepad) {
+            actions.press(action);
+        }
+
+        held += gamepad.dpad();
+        let stick = gamepad.left_stick();
+        if stick.length() >= GamepadRepeat::DEADZONE {
+            held += stick;
+        }
+    }
+
+    let direction = stick_direction(held);
+    if !repeat.update(direction, time.delta()) {
+        return;
+    }
+    let Some(direction) = direction else {
+        return;
+    };
+
+    if let Some((action, _)) = MOVE_ACTIONS.iter().find(|(_, dir)| *dir == direction) {
+        actions.press(*action);
     }
-    if keyboard.just_pressed(KeyCode::KeyS) {
-        commands.trigger(PlayerAction::Move(MoveDirection::South));
+    // The same push walks through menus
+    match direction {
+        MoveDirection::North => actions.press(InputAction::MenuUp),
+        MoveDirection::South => actions.press(InputAction::MenuDown),
+        MoveDirection::West => actions.press(InputAction::MenuLeft),
+        MoveDirection::East => actions.press(InputAction::MenuRight),
+        _ => {}
     }
-    if keyboard.just_pressed(KeyCode::KeyA) {
-        commands.trigger(PlayerAction::Move(MoveDirection::West));
+}
+
+/// Snaps a stick or d-pad vector (y up) to the nearest of the eight directions
+fn stick_direction(held: Vec2) -> Option<MoveDirection> {
+    if held.length() < GamepadRepeat::DEADZONE {
+        return None;
+    }
+
+    let octant = (held.y.atan2(held.x) / std::f32::consts::FRAC_PI_4).round() as i32;
+    Some(match octant.rem_euclid(8) {
+        0 => MoveDirection::East,
+        1 => MoveDirection::NorthEast,
+        2 => MoveDirection::North,
+        3 => MoveDirection::NorthWest,
+        4 => MoveDirection::West,
+        5 => MoveDirection::SouthWest,
+        6 => MoveDirection::South,
+        _ => MoveDirection::SouthEast,
+    })
+}
+
+/// Puts the cursor back on the first row whenever another menu opens
+pub fn reset_menu_cursor(mut cursor: ResMut<MenuCursor>) {
+    cursor.index = 0;
+}
+
+/// Movement directions in the order the move actions are checked
+const MOVE_ACTIONS: [(InputAction, MoveDirection); 8] = [
+    (InputAction::MoveNorth, MoveDirection::North),
+    (InputAction::MoveSouth, MoveDirection::South),
+    (InputAction::MoveEast, MoveDirection::East),
+    (InputAction::MoveWest, MoveDirection::West),
+    (InputAction::MoveNorthEast, MoveDirection::NorthEast),
+    (InputAction::MoveNorthWest, MoveDirection::NorthWest),
+    (InputAction::MoveSouthEast, MoveDirection::SouthEast),
+    (InputAction::MoveSouthWest, MoveDirection::SouthWest),
+];
+
+pub fn gameplay_input(
+    mut commands: Commands,
+    actions: Res<ActionInput>,
+    mut log_history: ResMut<LogHistory>,
+    mut inventory_menu: ResMut<InventoryMenu>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+    mut next_state: ResMut<NextState<RunningState>>,
+) {
+    if actions.just_pressed(InputAction::Cancel) {
+        next_state.set(RunningState::Paused);
+        return;
     }
-    if keyboard.just_pressed(KeyCode::KeyD) {
-        commands.trigger(PlayerAction::Move(MoveDirection::East));
+
+    // Movement
+    for (action, direction) in MOVE_ACTIONS {
+        if actions.just_pressed(action) {
+            commands.trigger(PlayerAction::Move(direction));
+        }
     }
 
-    // Wait
-    if keyboard.just_pressed(KeyCode::Space) {
+    if actions.just_pressed(InputAction::Wait) {
         commands.trigger(PlayerAction::Wait);
     }
 
-    // Pickup item
-    if keyboard.just_pressed(KeyCode::KeyG) {
-        commands.trigger(PlayerAction::PickupItem);
+    if actions.just_pressed(InputAction::Descend) {
+        commands.trigger(PlayerAction::Descend);
+    }
+    if actions.just_pressed(InputAction::Ascend) {
+        commands.trigger(PlayerAction::Ascend);
+    }
+
+    if actions.just_pressed(InputAction::PickUp) {
+        commands.trigger(PlayerAction::PickupItem(None));
+    }
+
+    // Inventory and equipment screens
+    if actions.just_pressed(InputAction::OpenInventory) {
+        inventory_menu.selected = None;
+        next_menu.set(MenuState::Inventory);
+    }
+    if actions.just_pressed(InputAction::OpenEquipment) {
+        next_menu.set(MenuState::Equipment);
+    }
+
+    // Message history, opened at the newest entry
+    if actions.just_pressed(InputAction::OpenMessageLog) {
+        log_history.scroll = 0;
+        next_menu.set(MenuState::LogHistory);
+    }
+}
+
+pub fn start_menu_input(
+    mut commands: Commands,
+    actions: Res<ActionInput>,
+    mut cursor: ResMut<MenuCursor>,
+    mut next_state: ResMut<NextState<RunningState>>,
+    mut next_options: ResMut<NextState<OptionsState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    cursor.navigate(&actions, MainMenuEntry::ALL.len());
+    let picked = if actions.just_pressed(InputAction::Confirm) {
+        MainMenuEntry::ALL.get(cursor.index).copied()
+    } else {
+        MainMenuEntry::ALL
+            .iter()
+            .copied()
+            .find(|entry| actions.just_pressed(entry.action()))
+    };
+
+    match picked {
+        Some(MainMenuEntry::NewGame) => next_state.set(RunningState::Load),
+        // Failures are reported on the menu by `LoadGame` itself
+        Some(MainMenuEntry::Continue) => commands.queue(LoadGame),
+        Some(MainMenuEntry::Options) => next_options.set(OptionsState::Open),
+        Some(MainMenuEntry::Quit) => {
+            exit.send(AppExit::Success);
+        }
+        None => {}
+    }
+}
+
+pub fn pause_menu_input(
+    mut commands: Commands,
+    actions: Res<ActionInput>,
+    mut cursor: ResMut<MenuCursor>,
+    mut next_state: ResMut<NextState<RunningState>>,
+    mut next_options: ResMut<NextState<OptionsState>>,
+) {
+    if actions.just_pressed(InputAction::Cancel) {
+        next_state.set(RunningState::Running);
+        return;
+    }
+
+    cursor.navigate(&actions, PauseEntry::ALL.len());
+    if !actions.just_pressed(InputAction::Confirm) {
+        return;
+    }
+
+    match PauseEntry::ALL.get(cursor.index) {
+        Some(PauseEntry::Resume) => next_state.set(RunningState::Running),
+        Some(PauseEntry::Options) => next_options.set(OptionsState::Open),
+        // The run can be picked up again with "Continue"
+        Some(PauseEntry::SaveAndQuit) => {
+            commands.queue(SaveGame);
+            next_state.set(RunningState::StartMenu);
+        }
+        None => {}
+    }
+}
+
+/// Edits `AppSettings` in place, every change is applied and saved right away.
+pub fn options_input(
+    actions: Res<ActionInput>,
+    mut settings: ResMut<AppSettings>,
+    mut cursor: ResMut<MenuCursor>,
+    mut next_options: ResMut<NextState<OptionsState>>,
+) {
+    cursor.navigate(&actions, OptionsEntry::ALL.len());
+    let Some(entry) = OptionsEntry::ALL.get(cursor.index).copied() else {
+        return;
+    };
+
+    if actions.just_pressed(InputAction::Cancel)
+        || (entry == OptionsEntry::Back && actions.just_pressed(InputAction::Confirm))
+    {
+        next_options.set(OptionsState::Closed);
+        return;
+    }
+
+    // Only touch the resource when a value really moves, the window follows its changes
+    let up = if actions.just_pressed(InputAction::MenuRight)
+        || actions.just_pressed(InputAction::Confirm)
+    {
+        true
+    } else if actions.just_pressed(InputAction::MenuLeft) {
+        false
+    } else {
+        return;
+    };
+    if entry.adjust(settings.bypass_change_detection(), up) {
+        settings.set_changed();
+    }
+}
+
+pub fn game_over_input(actions: Res<ActionInput>, mut next_state: ResMut<NextState<RunningState>>) {
+    // Restart with a fresh dungeon
+    if actions.just_pressed(InputAction::Confirm) {
+        next_state.set(RunningState::Load);
+    }
+}
+
+pub fn pickup_menu_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionInput>,
+    pickup_choices: Res<PickupChoices>,
+    mut cursor: ResMut<MenuCursor>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+) {
+    if actions.just_pressed(InputAction::Cancel) {
+        next_menu.set(MenuState::Closed);
+        return;
+    }
+
+    cursor.navigate(&actions, pickup_choices.0.len());
+    let picked = if actions.just_pressed(InputAction::Confirm) {
+        Some(cursor.index)
+    } else {
+        letter_pressed(&keyboard)
+    };
+
+    if let Some(item) = picked.and_then(|i| pickup_choices.0.get(i)) {
+        next_menu.set(MenuState::Closed);
+        commands.trigger(PlayerAction::PickupItem(Some(*item)));
+    }
+}
+
+pub fn targeting_input(
+    mut commands: Commands,
+    actions: Res<ActionInput>,
+    mut targeting: ResMut<Targeting>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+) {
+    if actions.just_pressed(InputAction::Cancel) {
+        targeting.item = None;
+        next_menu.set(MenuState::Closed);
+        return;
+    }
+
+    // Move the cursor
+    for (action, direction) in MOVE_ACTIONS {
+        if actions.just_pressed(action) {
+            targeting.cursor = targeting.cursor + direction.delta();
+        }
+    }
+
+    if actions.just_pressed(InputAction::Confirm) {
+        if let Some(item) = targeting.item.take() {
+            next_menu.set(MenuState::Closed);
+            commands.trigger(PlayerAction::UseItem(item, Some(targeting.cursor)));
+        }
+    }
+}
+
+pub fn log_history_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionInput>,
+    game_log: Res<GameLog>,
+    camera: Res<MapCamera>,
+    mut log_history: ResMut<LogHistory>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+) {
+    if actions.just_pressed(InputAction::Cancel)
+        || actions.just_pressed(InputAction::OpenMessageLog)
+    {
+        next_menu.set(MenuState::Closed);
+        return;
+    }
+
+    // Number keys toggle the categories in the order they are listed
+    for (key, category) in [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+    ]
+    .into_iter()
+    .zip(LogCategory::ALL)
+    {
+        if keyboard.just_pressed(key) {
+            log_history.toggle(category);
+        }
+    }
+
+    // Either action scrolls, a key bound to both only counts once
+    let page = LogHistory::page(camera.height);
+    let mut scroll = log_history.scroll as i64;
+    if actions.just_pressed(InputAction::MoveNorth) || actions.just_pressed(InputAction::MenuUp) {
+        scroll += 1;
+    }
+    if actions.just_pressed(InputAction::MoveSouth) || actions.just_pressed(InputAction::MenuDown) {
+        scroll -= 1;
+    }
+    if keyboard.just_pressed(KeyCode::PageUp) {
+        scroll += page as i64;
+    }
+    if keyboard.just_pressed(KeyCode::PageDown) {
+        scroll -= page as i64;
+    }
+
+    // Never scroll past the oldest entry
+    let shown = log_history.shown();
+    let lines = game_log.filtered(&shown).count();
+    let max_scroll = lines.saturating_sub(page);
+    let scroll = (scroll.max(0) as usize).min(max_scroll);
+    if log_history.scroll != scroll {
+        log_history.scroll = scroll;
+    }
+}
+
+pub fn inventory_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionInput>,
+    mut inventory_menu: ResMut<InventoryMenu>,
+    mut cursor: ResMut<MenuCursor>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+    player_query: Query<Entity, With<Player>>,
+    item_query: Query<(Entity, &InBackpack, Has<Consumable>, Has<Equippable>)>,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    let mut carried: Vec<Entity> = item_query
+        .iter()
+        .filter(|(_, backpack, _, _)| backpack.owner == player_entity)
+        .map(|(item, _, _, _)| item)
+        .collect();
+    // Same order as the inventory screen lists them
+    carried.sort();
+
+    // With an item selected the keys pick what to do with it
+    if let Some((item, _, consumable, equippable)) = inventory_menu
+        .selected
+        .and_then(|item| item_query.get(item).ok())
+    {
+        if actions.just_pressed(InputAction::Cancel) {
+            inventory_menu.selected = None;
+            cursor.index = carried.iter().position(|c| *c == item).unwrap_or(0);
+            return;
+        }
+
+        let item_commands = ItemCommand::available(consumable, equippable);
+        cursor.navigate(&actions, item_commands.len());
+        let chosen = if actions.just_pressed(InputAction::Confirm) {
+            item_commands.get(cursor.index).copied()
+        } else {
+            item_commands
+                .iter()
+                .copied()
+                .find(|command| keyboard.just_pressed(command.key()))
+        };
+
+        let action = match chosen {
+            Some(ItemCommand::Use) => PlayerAction::UseItem(item, None),
+            Some(ItemCommand::Equip) => PlayerAction::EquipItem(item),
+            Some(ItemCommand::Drop) => PlayerAction::DropItem(item),
+            None => return,
+        };
+
+        inventory_menu.selected = None;
+        next_menu.set(MenuState::Closed);
+        commands.trigger(action);
+        return;
+    }
+
+    // Letters pick items first, so the toggle key only closes when no item has its letter
+    let letter = letter_pressed(&keyboard).filter(|i| *i < carried.len());
+    if actions.just_pressed(InputAction::Cancel)
+        || (letter.is_none() && actions.just_pressed(InputAction::OpenInventory))
+    {
+        next_menu.set(MenuState::Closed);
+        return;
+    }
+
+    cursor.navigate(&actions, carried.len());
+    let picked = if actions.just_pressed(InputAction::Confirm) {
+        Some(cursor.index)
+    } else {
+        letter
+    };
+
+    if let Some(item) = picked.and_then(|i| carried.get(i)) {
+        inventory_menu.selected = Some(*item);
+        cursor.index = 0;
+    }
+}
+
+pub fn equipment_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionInput>,
+    mut cursor: ResMut<MenuCursor>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+    player_query: Query<Entity, With<Player>>,
+    equipped_query: Query<(Entity, &Equipped)>,
+) {
+    // Slots are lettered in `EquipmentSlot::ALL` order, picking a filled one takes it off.
+    // Letters come first, so the toggle key only closes when no slot has its letter.
+    let letter = letter_pressed(&keyboard).filter(|i| *i < EquipmentSlot::ALL.len());
+    if actions.just_pressed(InputAction::Cancel)
+        || (letter.is_none() && actions.just_pressed(InputAction::OpenEquipment))
+    {
+        next_menu.set(MenuState::Closed);
+        return;
+    }
+
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    cursor.navigate(&actions, EquipmentSlot::ALL.len());
+    let picked = if actions.just_pressed(InputAction::Confirm) {
+        Some(cursor.index)
+    } else {
+        letter
+    };
+    let Some(slot) = picked.and_then(|i| EquipmentSlot::ALL.get(i)) else {
+        return;
+    };
+    if let Some((item, _)) = equipped_query
+        .iter()
+        .find(|(_, equipped)| equipped.owner == player_entity && equipped.slot == *slot)
+    {
+        next_menu.set(MenuState::Closed);
+        commands.trigger(PlayerAction::UnequipItem(item));
     }
 }
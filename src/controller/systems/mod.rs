@@ -1,3 +1,5 @@
 mod input;
+mod mouse;
 
 pub use input::*;
+pub use mouse::*;
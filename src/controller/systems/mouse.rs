@@ -0,0 +1,148 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use bracket_lib::bevy::BracketContext;
+use bracket_lib::prelude::a_star_search;
+
+use crate::controller::events::PlayerAction;
+use crate::controller::resources::{ActionInput, MouseHover, Targeting, Travel};
+use crate::model::components::{FieldOfView, Monster, Player, Position};
+use crate::model::resources::{GameLog, LogCategory, Map};
+use crate::model::systems::MoveDirection;
+use crate::view::resources::MapCamera;
+use crate::MenuState;
+
+/// Finds the tile under the mouse through the console and the path the player would take to it.
+pub fn read_mouse(
+    ctx: Res<BracketContext>,
+    map: Res<Map>,
+    camera: Res<MapCamera>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    player_query: Query<&Position, With<Player>>,
+    mut hover: ResMut<MouseHover>,
+) {
+    let over_window = window_query
+        .get_single()
+        .is_ok_and(|window| window.cursor_position().is_some());
+    let mouse = ctx.get_mouse_position_for_current_layer();
+    let world = camera.to_world(mouse);
+
+    // Only tiles in view that the player knows about can be hovered
+    let tile = (over_window
+        && camera.to_screen(world).is_some()
+        && map.in_bounds(world.x, world.y)
+        && map.revealed_tiles[map.xy_idx(world.x, world.y)])
+    .then_some(world);
+    let from = player_query.get_single().ok().copied();
+
+    if hover.tile == tile && hover.from == from {
+        return;
+    }
+
+    let path = match (from, tile) {
+        (Some(from), Some(tile)) => find_path(&map, from, tile),
+        _ => Vec::new(),
+    };
+    *hover = MouseHover { tile, from, path };
+}
+
+/// A* over the map, keeping to revealed tiles so the preview gives nothing away
+fn find_path(map: &Map, from: Position, to: Position) -> Vec<Position> {
+    if from == to || map.is_blocked(to.x, to.y) {
+        return Vec::new();
+    }
+
+    let path = a_star_search(map.xy_idx(from.x, from.y), map.xy_idx(to.x, to.y), map);
+    if !path.success || path.steps.iter().any(|idx| !map.revealed_tiles[*idx]) {
+        return Vec::new();
+    }
+
+    path.steps
+        .iter()
+        .skip(1)
+        .map(|idx| {
+            let (x, y) = map.idx_xy(*idx);
+            Position::new(x, y)
+        })
+        .collect()
+}
+
+/// A left click starts walking to the hovered tile, replacing any walk in progress.
+pub fn mouse_travel_input(
+    mouse: Res<ButtonInput<MouseButton>>,
+    hover: Res<MouseHover>,
+    mut travel: ResMut<Travel>,
+) {
+    if mouse.just_pressed(MouseButton::Left) && !hover.path.is_empty() {
+        travel.path = hover.path.iter().copied().collect();
+    }
+}
+
+/// Takes the next step of a click-to-travel walk.
+///
+/// The walk ends on any other input, when a monster is in view or when a step didn't go as planned.
+pub fn travel_step(
+    mut commands: Commands,
+    actions: Res<ActionInput>,
+    mut travel: ResMut<Travel>,
+    mut game_log: ResMut<GameLog>,
+    player_query: Query<(&Position, &FieldOfView), With<Player>>,
+    monster_query: Query<&Position, With<Monster>>,
+) {
+    if !travel.is_active() {
+        return;
+    }
+
+    let Ok((position, fov)) = player_query.get_single() else {
+        travel.stop();
+        return;
+    };
+
+    if actions.any() {
+        travel.stop();
+        return;
+    }
+
+    if monster_query.iter().any(|monster| fov.is_visible(monster)) {
+        travel.stop();
+        game_log.add_entry_srgb(
+            LogCategory::Movement,
+            "You stop, there is a monster in view.",
+            Color::srgb(1.0, 0.6, 0.0),
+        );
+        return;
+    }
+
+    let Some(next) = travel.path.pop_front() else {
+        return;
+    };
+    match MoveDirection::from_delta((next.x - position.x, next.y - position.y)) {
+        Some(direction) => commands.trigger(PlayerAction::Move(direction)),
+        // Knocked off the path, e.g. by stumbling while confused
+        None => travel.stop(),
+    }
+}
+
+/// Aims with the mouse while targeting, a left click fires at the hovered tile.
+pub fn mouse_targeting_input(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    hover: Res<MouseHover>,
+    mut targeting: ResMut<Targeting>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+) {
+    let Some(tile) = hover.tile else {
+        return;
+    };
+
+    // Only follow the mouse when it moves, so aiming with the keys isn't undone
+    if cursor_moved.read().count() > 0 {
+        targeting.cursor = tile;
+    }
+
+    if mouse.just_pressed(MouseButton::Left) {
+        if let Some(item) = targeting.item.take() {
+            next_menu.set(MenuState::Closed);
+            commands.trigger(PlayerAction::UseItem(item, Some(tile)));
+        }
+    }
+}
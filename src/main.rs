@@ -33,7 +33,8 @@ fn main() {
     let mut app = App::new();
 
     // Load AppSettings
-    let app_settings = AppSettings::default();
+    let mut app_settings = AppSettings::load();
+    app_settings.apply_args(std::env::args().skip(1));
 
     app.add_plugins(
         DefaultPlugins
@@ -61,6 +62,9 @@ fn main() {
             .set(ImagePlugin::default_nearest()),
     )
     .init_state::<RunningState>()
+    .add_sub_state::<TurnPhase>()
+    .add_sub_state::<MenuState>()
+    .init_state::<OptionsState>()
     .configure_sets(
         Update,
         (
@@ -72,7 +76,11 @@ fn main() {
             .chain(),
     );
 
-    app.insert_resource(app_settings);
+    app.insert_resource(app_settings).add_systems(
+        Last,
+        save_app_settings
+            .run_if(resource_changed::<AppSettings>.and(not(resource_added::<AppSettings>))),
+    );
 
     #[cfg(feature = "dev")]
     app.add_plugins(crate::dev::DevPlugin);
@@ -96,8 +104,49 @@ enum AppSet {
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
 enum RunningState {
+    /// Choosing between a new game and continuing a saved one
     #[default]
+    StartMenu,
     Load,
     Paused,
     Running,
+    /// The player died; waiting for a restart
+    GameOver,
+}
+
+/// Who gets to act while the game is running.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, SubStates)]
+#[source(RunningState = RunningState::Running)]
+enum TurnPhase {
+    /// The player has enough energy and the world waits for their input.
+    AwaitingInput,
+    /// Energy accrues and monsters act until the player is ready again.
+    #[default]
+    MonsterTurn,
+}
+
+/// Modal menu shown over the map while the player decides what to do.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, SubStates)]
+#[source(TurnPhase = TurnPhase::AwaitingInput)]
+enum MenuState {
+    #[default]
+    Closed,
+    /// Choosing one of several items on the player's tile
+    PickupItem,
+    /// Aiming a ranged item at a tile
+    Targeting,
+    /// Reading back through the message log
+    LogHistory,
+    /// Browsing the backpack and acting on an item
+    Inventory,
+    /// Looking over what is worn in each slot
+    Equipment,
+}
+
+/// Options screen, opened over the start menu or the pause screen.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+enum OptionsState {
+    #[default]
+    Closed,
+    Open,
 }
@@ -1,2 +1,2 @@
-mod try_move;
-pub use self::try_move::*;
+mod save_game;
+pub use self::save_game::*;
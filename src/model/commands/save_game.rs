@@ -0,0 +1,199 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*, scene::serde::SceneDeserializer};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+use std::{fmt, fs, io, path::PathBuf};
+
+use crate::model::components::{Energy, Item, MapLevel, Player};
+use crate::model::resources::{Dungeon, GameLog, GameRng, Map, SaveStatus, StatusClock, TurnState};
+use crate::RunningState;
+
+/// Bumped whenever a change to the saved components or resources breaks old saves
+const SAVE_VERSION: u32 = 2;
+const SAVE_PATH: &str = "saves";
+const SAVE_FILE_NAME: &str = "savegame";
+
+/// What ends up on disk: a version header around a reflected scene of the run
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    seed: u64,
+    scene: String,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    NotFound,
+    Io(io::Error),
+    Corrupt(String),
+    Incompatible { found: u32 },
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::NotFound => write!(f, "There is no saved game."),
+            SaveError::Io(err) => write!(f, "The save file could not be accessed: {err}"),
+            SaveError::Corrupt(reason) => write!(f, "The save file is corrupt: {reason}"),
+            SaveError::Incompatible { found } => write!(
+                f,
+                "The save file is from an incompatible version ({found}, expected {SAVE_VERSION})."
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::NotFound => SaveError::NotFound,
+            _ => SaveError::Io(err),
+        }
+    }
+}
+
+pub fn save_file_path() -> PathBuf {
+    let mut file_path = PathBuf::from(SAVE_PATH);
+    file_path.push(SAVE_FILE_NAME);
+    file_path.set_extension("ron");
+    file_path
+}
+
+pub fn save_exists() -> bool {
+    save_file_path().is_file()
+}
+
+/// Removes the save, a dead hero stays dead
+pub fn delete_save() {
+    if let Err(err) = fs::remove_file(save_file_path()) {
+        if err.kind() != io::ErrorKind::NotFound {
+            error!("Failed to delete save: {err}");
+        }
+    }
+}
+
+/// Writes the current run to disk.
+pub struct SaveGame;
+
+impl Command for SaveGame {
+    fn apply(self, world: &mut World) {
+        let message = match save_world(world) {
+            Ok(()) => {
+                info!("Game saved");
+                "Game saved.".to_string()
+            }
+            Err(err) => {
+                error!("Failed to save game: {err}");
+                err.to_string()
+            }
+        };
+        world.resource_mut::<SaveStatus>().message = Some(message);
+    }
+}
+
+/// Replaces the current run with the saved one and resumes it.
+pub struct LoadGame;
+
+impl Command for LoadGame {
+    fn apply(self, world: &mut World) {
+        match load_world(world) {
+            Ok(()) => {
+                info!("Game loaded");
+                world.resource_mut::<SaveStatus>().message = None;
+                world
+                    .resource_mut::<NextState<RunningState>>()
+                    .set(RunningState::Running);
+            }
+            Err(err) => {
+                error!("Failed to load game: {err}");
+                world.resource_mut::<SaveStatus>().message = Some(err.to_string());
+            }
+        }
+    }
+}
+
+fn game_entities(world: &mut World) -> Vec<Entity> {
+    world
+        .query_filtered::<Entity, Or<(With<Player>, With<MapLevel>, With<Item>)>>()
+        .iter(world)
+        .collect()
+}
+
+fn save_world(world: &mut World) -> Result<(), SaveError> {
+    let entities = game_entities(world);
+    let scene = DynamicSceneBuilder::from_world(world)
+        .deny_all_resources()
+        .allow_resource::<Map>()
+        .allow_resource::<TurnState>()
+        .allow_resource::<GameLog>()
+        .allow_resource::<Dungeon>()
+        .extract_entities(entities.into_iter())
+        .extract_resources()
+        .build();
+
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let save = SaveFile {
+        version: SAVE_VERSION,
+        seed: world.resource::<GameRng>().seed(),
+        scene: scene
+            .serialize(&type_registry)
+            .map_err(|err| SaveError::Corrupt(err.to_string()))?,
+    };
+    let contents = ron::ser::to_string_pretty(&save, ron::ser::PrettyConfig::default())
+        .map_err(|err| SaveError::Corrupt(err.to_string()))?;
+
+    fs::create_dir_all(SAVE_PATH)?;
+    fs::write(save_file_path(), contents)?;
+    Ok(())
+}
+
+fn load_world(world: &mut World) -> Result<(), SaveError> {
+    let contents = fs::read_to_string(save_file_path())?;
+
+    // Check the header before trying to make sense of the scene
+    let save: SaveFile =
+        ron::from_str(&contents).map_err(|err| SaveError::Corrupt(err.to_string()))?;
+    if save.version != SAVE_VERSION {
+        return Err(SaveError::Incompatible {
+            found: save.version,
+        });
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let scene = {
+        let type_registry = type_registry.read();
+        let mut deserializer = ron::de::Deserializer::from_str(&save.scene)
+            .map_err(|err| SaveError::Corrupt(err.to_string()))?;
+        SceneDeserializer {
+            type_registry: &type_registry,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|err| SaveError::Corrupt(err.to_string()))?
+    };
+
+    // Nothing from the previous run may survive
+    for entity in game_entities(world) {
+        world.entity_mut(entity).despawn_recursive();
+    }
+
+    // Saved entity ids are remapped onto fresh entities, `MapEntities` fixes the references
+    scene
+        .write_to_world(world, &mut EntityHashMap::default())
+        .map_err(|err| SaveError::Corrupt(err.to_string()))?;
+
+    world.resource_mut::<GameRng>().resume(save.seed);
+
+    // The turn in progress goes back to the player as energy
+    let (action_points, turn_number) = {
+        let mut turn_state = world.resource_mut::<TurnState>();
+        let points = turn_state.action_points;
+        turn_state.clear_current_entity();
+        (points, turn_state.turn_number)
+    };
+    // Statuses were counted down up to the saved turn
+    world.resource_mut::<StatusClock>().last_tick = turn_number;
+    let mut player_query = world.query_filtered::<&mut Energy, With<Player>>();
+    if let Ok(mut energy) = player_query.get_single_mut(world) {
+        energy.0 = energy.0.max(action_points);
+    }
+
+    Ok(())
+}
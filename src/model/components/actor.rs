@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use super::effect::StatusEffects;
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
 #[reflect(Component)]
 pub struct Player;
@@ -14,6 +16,7 @@ pub struct NPC;
 
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
+#[require(Energy, EffectiveStats, StatusEffects)]
 pub struct Stats {
     pub health: i32,
     pub max_health: i32,
@@ -33,3 +36,17 @@ impl Stats {
         }
     }
 }
+
+/// `Stats` with equipment bonuses applied. Derived every frame, never edit it directly.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct EffectiveStats {
+    pub attack: i32,
+    pub defense: i32,
+    pub speed: i32,
+}
+
+/// Energy accrued from `EffectiveStats::speed`; an actor may act once it holds `ModelConstants::ACTION_COST`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Energy(pub i32);
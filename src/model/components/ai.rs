@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+use super::position::Position;
+
+/// A single behaviour a monster can fall back on. Behaviours are tried in order and the
+/// first one that produces an action wins.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum Behavior {
+    /// Run away from the player while health is below `health_fraction` of max health
+    Flee { health_fraction: f32 },
+    /// Path toward the player, or toward where the player was last seen
+    Chase,
+    /// Take a random step `chance` percent of the time
+    Wander { chance: i32 },
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Ai {
+    pub behaviors: Vec<Behavior>,
+    pub last_seen_player: Option<Position>,
+}
+
+impl Ai {
+    pub fn new(behaviors: impl Into<Vec<Behavior>>) -> Self {
+        Self {
+            behaviors: behaviors.into(),
+            last_seen_player: None,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+/// Restores health to whoever the item is used on
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ProvidesHealing {
+    pub amount: i32,
+}
+
+/// Damages whoever the item is used on
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct InflictsDamage {
+    pub amount: i32,
+}
+
+/// Afflicts whoever the item is used on with a status for `duration` turns
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct InflictsStatus {
+    pub kind: StatusKind,
+    pub duration: i32,
+}
+
+/// The item must be aimed at a tile at most `range` tiles away
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Ranged {
+    pub range: i32,
+}
+
+/// The item affects every actor within `radius` of the target tile
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct AreaOfEffect {
+    pub radius: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum StatusKind {
+    /// Stumbles around at random
+    Confused,
+    /// Loses a point of health every turn
+    Poisoned,
+}
+
+impl StatusKind {
+    pub fn adjective(&self) -> &'static str {
+        match self {
+            StatusKind::Confused => "confused",
+            StatusKind::Poisoned => "poisoned",
+        }
+    }
+}
+
+/// Statuses currently afflicting an actor and the world turns each has left
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct StatusEffects {
+    pub remaining: Vec<(StatusKind, i32)>,
+}
+
+impl StatusEffects {
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.remaining.iter().any(|(active, _)| *active == kind)
+    }
+
+    /// Applies `kind`, extending it if it is already active
+    pub fn add(&mut self, kind: StatusKind, duration: i32) {
+        match self
+            .remaining
+            .iter_mut()
+            .find(|(active, _)| *active == kind)
+        {
+            Some((_, turns)) => *turns = (*turns).max(duration),
+            None => self.remaining.push((kind, duration)),
+        }
+    }
+}
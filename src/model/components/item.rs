@@ -1,4 +1,7 @@
-use bevy::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
 #[reflect(Component)]
@@ -19,6 +22,30 @@ pub enum EquipmentSlot {
     OffHand,
 }
 
+impl EquipmentSlot {
+    pub const ALL: [EquipmentSlot; 7] = [
+        EquipmentSlot::Head,
+        EquipmentSlot::Torso,
+        EquipmentSlot::Legs,
+        EquipmentSlot::Feet,
+        EquipmentSlot::Hands,
+        EquipmentSlot::MainHand,
+        EquipmentSlot::OffHand,
+    ];
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            EquipmentSlot::Head => "Head",
+            EquipmentSlot::Torso => "Torso",
+            EquipmentSlot::Legs => "Legs",
+            EquipmentSlot::Feet => "Feet",
+            EquipmentSlot::Hands => "Hands",
+            EquipmentSlot::MainHand => "Main hand",
+            EquipmentSlot::OffHand => "Off hand",
+        }
+    }
+}
+
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
 pub struct Equippable {
@@ -26,14 +53,35 @@ pub struct Equippable {
 }
 
 #[derive(Component, Debug, Clone, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, MapEntities)]
 pub struct InBackpack {
     pub owner: Entity,
 }
 
+impl MapEntities for InBackpack {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.owner = entity_mapper.map_entity(self.owner);
+    }
+}
+
 #[derive(Component, Debug, Clone, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, MapEntities)]
 pub struct Equipped {
     pub owner: Entity,
     pub slot: EquipmentSlot,
 }
+
+impl MapEntities for Equipped {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.owner = entity_mapper.map_entity(self.owner);
+    }
+}
+
+/// Bonuses granted to the wearer while the item is equipped
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct StatBonus {
+    pub attack: i32,
+    pub defense: i32,
+    pub speed: i32,
+}
@@ -1,5 +1,7 @@
 mod actor;
+mod ai;
 mod description;
+mod effect;
 mod field_of_view;
 mod item;
 mod position;
@@ -7,7 +9,9 @@ mod renderable;
 mod terrain;
 
 pub use actor::*;
+pub use ai::*;
 pub use description::*;
+pub use effect::*;
 pub use field_of_view::*;
 pub use item::*;
 pub use position::*;
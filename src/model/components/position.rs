@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use std::ops::Add;
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
 #[reflect(Component)]
 pub struct Position {
     pub x: i32,
@@ -28,3 +28,8 @@ impl Add<(i32, i32)> for Position {
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
 #[reflect(Component)]
 pub struct MapLevel(pub i32);
+
+/// Where an entity was left on a dungeon level the player is not currently on
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct StashedPosition(pub Position);
@@ -1,23 +1,34 @@
 use bevy::prelude::*;
+use serde::Deserialize;
 
 use crate::model::components::Description;
 
-#[derive(Component, Reflect, Default, Clone, Debug, PartialEq)]
+#[derive(Component, Reflect, Default, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
 #[reflect(Component)]
 #[require(Description)]
+#[serde(rename_all = "snake_case")]
 pub enum TerrainType {
     #[default]
     Floor,
     Wall,
-    Stairs,
+    DownStairs,
+    UpStairs,
+    /// Walked through freely, but nothing can be seen through it
+    Door,
 }
 
 impl TerrainType {
     pub fn is_walkable(&self) -> bool {
-        matches!(self, TerrainType::Floor | TerrainType::Stairs)
+        matches!(
+            self,
+            TerrainType::Floor
+                | TerrainType::DownStairs
+                | TerrainType::UpStairs
+                | TerrainType::Door
+        )
     }
 
     pub fn is_opaque(&self) -> bool {
-        matches!(self, TerrainType::Wall)
+        matches!(self, TerrainType::Wall | TerrainType::Door)
     }
 }
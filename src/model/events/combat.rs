@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+/// `attacker` tries to hit `target` in melee
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttackIntent {
+    pub attacker: Entity,
+    pub target: Entity,
+}
+
+/// `target` lost `amount` health because of `source`, if anyone
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DamageDealt {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: i32,
+}
+
+/// `entity` ran out of health
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Death {
+    pub entity: Entity,
+    pub killer: Option<Entity>,
+}
@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use crate::model::components::Position;
+
+/// `user` used `item`, aimed at `target` when the item is ranged
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemUsed {
+    pub user: Entity,
+    pub item: Entity,
+    pub target: Option<Position>,
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// The player takes the stairs to the level at `depth`
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeLevel {
+    pub depth: i32,
+}
@@ -0,0 +1,7 @@
+mod combat;
+mod item;
+mod level;
+
+pub use combat::*;
+pub use item::*;
+pub use level::*;
@@ -0,0 +1,63 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{nearest_floor, MapBuild, MetaMapBuilder};
+use crate::model::components::Position;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XStart {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YStart {
+    Top,
+    Center,
+    Bottom,
+}
+
+/// Starts the player on the floor tile closest to a corner, an edge or the middle of the map.
+#[derive(Debug, Clone, Copy)]
+pub struct AreaStartingPosition {
+    pub x: XStart,
+    pub y: YStart,
+}
+
+impl AreaStartingPosition {
+    pub const fn new(x: XStart, y: YStart) -> Self {
+        Self { x, y }
+    }
+
+    /// One of the four corners
+    pub fn random_corner(rng: &mut RandomNumberGenerator) -> Self {
+        let x = if rng.range(0, 2) == 0 {
+            XStart::Left
+        } else {
+            XStart::Right
+        };
+        let y = if rng.range(0, 2) == 0 {
+            YStart::Top
+        } else {
+            YStart::Bottom
+        };
+        Self::new(x, y)
+    }
+}
+
+impl MetaMapBuilder for AreaStartingPosition {
+    fn apply(&self, build: &mut MapBuild, _rng: &mut RandomNumberGenerator) {
+        let map = &build.map;
+        let x = match self.x {
+            XStart::Left => 1,
+            XStart::Center => map.width / 2,
+            XStart::Right => map.width - 2,
+        };
+        let y = match self.y {
+            YStart::Top => 1,
+            YStart::Center => map.height / 2,
+            YStart::Bottom => map.height - 2,
+        };
+        build.start = Some(nearest_floor(map, Position::new(x, y)));
+    }
+}
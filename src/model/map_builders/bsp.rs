@@ -0,0 +1,85 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{apply_l_tunnel, apply_room_to_map, MapBuild, MapBuilder, Room};
+
+/// Binary space partition: the map is cut in two over and over and each leaf gets a room.
+///
+/// Rooms are joined in the order the leaves were cut, which keeps corridors short.
+#[derive(Debug, Clone)]
+pub struct BspBuilder {
+    /// Leaves are never cut smaller than this
+    pub min_leaf: i32,
+    /// Leaves larger than this are always cut
+    pub max_leaf: i32,
+    pub min_room: i32,
+}
+
+impl Default for BspBuilder {
+    fn default() -> Self {
+        Self {
+            min_leaf: 8,
+            max_leaf: 20,
+            min_room: 5,
+        }
+    }
+}
+
+impl BspBuilder {
+    fn split(&self, area: Room, rng: &mut RandomNumberGenerator, leaves: &mut Vec<Room>) {
+        let (w, h) = (area.width(), area.height());
+        let can_split_x = w >= self.min_leaf * 2;
+        let can_split_y = h >= self.min_leaf * 2;
+        let wants_split = w > self.max_leaf || h > self.max_leaf || rng.range(0, 4) == 0;
+
+        if !wants_split || !(can_split_x || can_split_y) {
+            leaves.push(area);
+            return;
+        }
+
+        // Cut across the longer side so leaves stay roughly square
+        let split_x = can_split_x && (!can_split_y || w >= h);
+        if split_x {
+            let cut = rng.range(self.min_leaf, w - self.min_leaf + 1);
+            self.split(Room::new(area.x1, area.y1, cut, h), rng, leaves);
+            self.split(Room::new(area.x1 + cut, area.y1, w - cut, h), rng, leaves);
+        } else {
+            let cut = rng.range(self.min_leaf, h - self.min_leaf + 1);
+            self.split(Room::new(area.x1, area.y1, w, cut), rng, leaves);
+            self.split(Room::new(area.x1, area.y1 + cut, w, h - cut), rng, leaves);
+        }
+    }
+}
+
+impl MapBuilder for BspBuilder {
+    fn build(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator) {
+        let map = &mut build.map;
+        let mut leaves = Vec::new();
+        self.split(
+            Room::new(0, 0, map.width - 1, map.height - 1),
+            rng,
+            &mut leaves,
+        );
+
+        let mut rooms: Vec<Room> = Vec::new();
+        for leaf in leaves {
+            // A small map can leave a leaf narrower than the smallest room
+            if leaf.width() < self.min_room || leaf.height() < self.min_room {
+                continue;
+            }
+
+            let w = rng.range(self.min_room, leaf.width() + 1);
+            let h = rng.range(self.min_room, leaf.height() + 1);
+            let x = leaf.x1 + rng.range(0, leaf.width() - w + 1);
+            let y = leaf.y1 + rng.range(0, leaf.height() - h + 1);
+            let room = Room::new(x, y, w, h);
+
+            apply_room_to_map(map, &room);
+            if let Some(prev) = rooms.last() {
+                apply_l_tunnel(map, prev.center(), room.center(), rng.range(0, 2) == 1);
+            }
+            rooms.push(room);
+        }
+
+        build.rooms = Some(rooms);
+    }
+}
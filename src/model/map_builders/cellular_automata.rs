@@ -0,0 +1,60 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{in_interior, MapBuild, MapBuilder};
+use crate::model::components::TerrainType;
+
+/// Caves grown from noise: a tile turns to wall when most of its neighbours are walls.
+#[derive(Debug, Clone)]
+pub struct CellularAutomataBuilder {
+    /// Percent of tiles that start out as floor
+    pub floor_chance: i32,
+    pub iterations: i32,
+}
+
+impl Default for CellularAutomataBuilder {
+    fn default() -> Self {
+        Self {
+            floor_chance: 55,
+            iterations: 15,
+        }
+    }
+}
+
+impl MapBuilder for CellularAutomataBuilder {
+    fn build(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator) {
+        let map = &mut build.map;
+
+        for y in 1..map.height - 1 {
+            for x in 1..map.width - 1 {
+                if rng.range(0, 100) < self.floor_chance {
+                    let idx = map.xy_idx(x, y);
+                    map.tiles[idx] = TerrainType::Floor;
+                }
+            }
+        }
+
+        for _ in 0..self.iterations {
+            let mut next = map.tiles.clone();
+            for y in 1..map.height - 1 {
+                for x in 1..map.width - 1 {
+                    let walls = (-1..=1)
+                        .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                        .filter(|(dx, dy)| (*dx, *dy) != (0, 0))
+                        .filter(|(dx, dy)| {
+                            !in_interior(map, x + dx, y + dy)
+                                || *map.get_tile(x + dx, y + dy) == TerrainType::Wall
+                        })
+                        .count();
+
+                    // Open areas with no walls at all get pillars to break them up
+                    next[map.xy_idx(x, y)] = if walls > 4 || walls == 0 {
+                        TerrainType::Wall
+                    } else {
+                        TerrainType::Floor
+                    };
+                }
+            }
+            map.tiles = next;
+        }
+    }
+}
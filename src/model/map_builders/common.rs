@@ -0,0 +1,271 @@
+use bracket_lib::prelude::DijkstraMap;
+
+use crate::model::components::{Position, TerrainType};
+use crate::model::resources::Map;
+
+/// Side of the square cells open levels are split into for spawning
+pub const REGION_SIZE: i32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Room {
+    pub x1: i32,
+    pub y1: i32,
+    pub x2: i32,
+    pub y2: i32,
+}
+
+impl Room {
+    pub fn new(x: i32, y: i32, w: i32, h: i32) -> Self {
+        Self {
+            x1: x,
+            y1: y,
+            x2: x + w,
+            y2: y + h,
+        }
+    }
+
+    pub fn intersect(&self, other: &Room) -> bool {
+        self.x1 <= other.x2 && self.x2 >= other.x1 && self.y1 <= other.y2 && self.y2 >= other.y1
+    }
+
+    pub fn center(&self) -> (i32, i32) {
+        ((self.x1 + self.x2) / 2, (self.y1 + self.y2) / 2)
+    }
+
+    pub const fn width(&self) -> i32 {
+        self.x2 - self.x1
+    }
+
+    pub const fn height(&self) -> i32 {
+        self.y2 - self.y1
+    }
+
+    /// The floor tiles `apply_room_to_map` carves, the edges stay wall
+    pub fn interior(&self) -> Vec<Position> {
+        (self.y1 + 1..self.y2)
+            .flat_map(|y| (self.x1 + 1..self.x2).map(move |x| Position::new(x, y)))
+            .collect()
+    }
+}
+
+pub fn apply_room_to_map(map: &mut Map, room: &Room) {
+    for Position { x, y } in room.interior() {
+        let idx = map.xy_idx(x, y);
+        map.tiles[idx] = TerrainType::Floor;
+    }
+}
+
+pub fn apply_horizontal_tunnel(map: &mut Map, x1: i32, x2: i32, y: i32) {
+    for x in i32::min(x1, x2)..=i32::max(x1, x2) {
+        if map.in_bounds(x, y) {
+            let idx = map.xy_idx(x, y);
+            map.tiles[idx] = TerrainType::Floor;
+        }
+    }
+}
+
+pub fn apply_vertical_tunnel(map: &mut Map, y1: i32, y2: i32, x: i32) {
+    for y in i32::min(y1, y2)..=i32::max(y1, y2) {
+        if map.in_bounds(x, y) {
+            let idx = map.xy_idx(x, y);
+            map.tiles[idx] = TerrainType::Floor;
+        }
+    }
+}
+
+/// An L-shaped corridor between two points, bending one way or the other
+pub fn apply_l_tunnel(map: &mut Map, from: (i32, i32), to: (i32, i32), horizontal_first: bool) {
+    if horizontal_first {
+        apply_horizontal_tunnel(map, from.0, to.0, from.1);
+        apply_vertical_tunnel(map, from.1, to.1, to.0);
+    } else {
+        apply_vertical_tunnel(map, from.1, to.1, from.0);
+        apply_horizontal_tunnel(map, from.0, to.0, to.1);
+    }
+}
+
+/// Whether `(x, y)` is inside the map and off its outer edge, which always stays wall
+pub fn in_interior(map: &Map, x: i32, y: i32) -> bool {
+    x > 0 && x < map.width - 1 && y > 0 && y < map.height - 1
+}
+
+pub fn floor_count(map: &Map) -> usize {
+    map.tiles
+        .iter()
+        .filter(|tile| **tile == TerrainType::Floor)
+        .count()
+}
+
+/// The walkable tile closest to `target`, `target` itself on a map without any
+pub fn nearest_floor(map: &Map, target: Position) -> Position {
+    map.tiles
+        .iter()
+        .enumerate()
+        .filter(|(_, tile)| tile.is_walkable())
+        .map(|(idx, _)| {
+            let (x, y) = map.idx_xy(idx);
+            Position::new(x, y)
+        })
+        .min_by_key(|pos| (pos.x - target.x).pow(2) + (pos.y - target.y).pow(2))
+        .unwrap_or(target)
+}
+
+/// Walking distance of every tile from `start`, `f32::MAX` where it can't be reached
+pub fn distances_from(map: &Map, start: Position) -> Vec<f32> {
+    let start = map.xy_idx(start.x, start.y);
+    DijkstraMap::new(map.width, map.height, &[start], map, 2048.0).map
+}
+
+/// Fills in every floor tile that can't be walked to from `start`
+pub fn cull_unreachable(map: &mut Map, start: Position) {
+    let distances = distances_from(map, start);
+    for (tile, distance) in map.tiles.iter_mut().zip(distances) {
+        if tile.is_walkable() && distance == f32::MAX {
+            *tile = TerrainType::Wall;
+        }
+    }
+}
+
+/// Joins every separate patch of walkable tiles to the biggest one with a corridor
+pub fn connect_regions(map: &mut Map) {
+    let mut regions = walkable_regions(map);
+    regions.sort_by_key(|region| std::cmp::Reverse(region.len()));
+    let Some((main, rest)) = regions.split_first() else {
+        return;
+    };
+
+    let mut main = main.clone();
+    for region in rest {
+        let from = region[0];
+        let Some(to) = main
+            .iter()
+            .min_by_key(|pos| (pos.x - from.x).pow(2) + (pos.y - from.y).pow(2))
+            .copied()
+        else {
+            continue;
+        };
+        apply_l_tunnel(map, (from.x, from.y), (to.x, to.y), from.x % 2 == 0);
+        main.extend(region);
+    }
+}
+
+/// Walkable tiles grouped by which of them touch, side by side
+fn walkable_regions(map: &Map) -> Vec<Vec<Position>> {
+    let mut seen = vec![false; map.tiles.len()];
+    let mut regions = Vec::new();
+
+    for idx in 0..map.tiles.len() {
+        if seen[idx] || !map.tiles[idx].is_walkable() {
+            continue;
+        }
+
+        let mut region = Vec::new();
+        let mut stack = vec![idx];
+        seen[idx] = true;
+        while let Some(current) = stack.pop() {
+            let (x, y) = map.idx_xy(current);
+            region.push(Position::new(x, y));
+            for (nx, ny) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+                if !map.in_bounds(nx, ny) {
+                    continue;
+                }
+                let next = map.xy_idx(nx, ny);
+                if !seen[next] && map.tiles[next].is_walkable() {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        regions.push(region);
+    }
+    regions
+}
+
+/// The reachable tile the longest walk away from `start`
+pub fn farthest_from(map: &Map, start: Position) -> Position {
+    distances_from(map, start)
+        .iter()
+        .enumerate()
+        .filter(|(idx, distance)| **distance < f32::MAX && map.tiles[*idx].is_walkable())
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(idx, _)| {
+            let (x, y) = map.idx_xy(idx);
+            Position::new(x, y)
+        })
+        .unwrap_or(start)
+}
+
+/// The floor inside each room, rooms left without any are skipped
+pub fn room_regions(map: &Map, rooms: &[Room]) -> Vec<Vec<Position>> {
+    rooms
+        .iter()
+        .map(|room| {
+            room.interior()
+                .into_iter()
+                .filter(|pos| *map.get_tile(pos.x, pos.y) == TerrainType::Floor)
+                .collect::<Vec<_>>()
+        })
+        .filter(|region| !region.is_empty())
+        .collect()
+}
+
+/// Splits the floor into square cells of `size`, skipping cells without any
+pub fn grid_regions(map: &Map, size: i32) -> Vec<Vec<Position>> {
+    let mut regions = Vec::new();
+    for cell_y in (0..map.height).step_by(size as usize) {
+        for cell_x in (0..map.width).step_by(size as usize) {
+            let region: Vec<Position> = (cell_y..(cell_y + size).min(map.height))
+                .flat_map(|y| (cell_x..(cell_x + size).min(map.width)).map(move |x| (x, y)))
+                .filter(|(x, y)| *map.get_tile(*x, *y) == TerrainType::Floor)
+                .map(|(x, y)| Position::new(x, y))
+                .collect();
+            if !region.is_empty() {
+                regions.push(region);
+            }
+        }
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::map_builders::MapBuild;
+
+    /// Two rooms whose only link is a diagonal step between two walls
+    fn squeezed_rooms() -> MapBuild {
+        let mut build = MapBuild::new(7, 7, 1);
+        for (x, y) in [
+            (1, 1),
+            (2, 1),
+            (1, 2),
+            (2, 2),
+            (3, 3),
+            (4, 3),
+            (3, 4),
+            (4, 4),
+        ] {
+            let idx = build.map.xy_idx(x, y);
+            build.map.tiles[idx] = TerrainType::Floor;
+        }
+        build
+    }
+
+    #[test]
+    fn culling_treats_squeezes_as_blocked() {
+        let mut build = squeezed_rooms();
+        cull_unreachable(&mut build.map, Position::new(1, 1));
+
+        assert_eq!(*build.map.get_tile(2, 2), TerrainType::Floor);
+        assert_eq!(*build.map.get_tile(3, 3), TerrainType::Wall);
+        assert_eq!(*build.map.get_tile(4, 4), TerrainType::Wall);
+    }
+
+    #[test]
+    fn stairs_never_land_behind_a_squeeze() {
+        let build = squeezed_rooms();
+        let stairs = farthest_from(&build.map, Position::new(1, 1));
+
+        assert!(stairs.x <= 2 && stairs.y <= 2, "stairs at {stairs:?}");
+    }
+}
@@ -0,0 +1,18 @@
+use bevy::log::warn;
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{cull_unreachable, MapBuild, MetaMapBuilder};
+
+/// Walls off every floor tile the player can't walk to from the start.
+#[derive(Debug, Clone, Copy)]
+pub struct CullUnreachable;
+
+impl MetaMapBuilder for CullUnreachable {
+    fn apply(&self, build: &mut MapBuild, _rng: &mut RandomNumberGenerator) {
+        let Some(start) = build.start else {
+            warn!("CullUnreachable needs a start position, skipping it");
+            return;
+        };
+        cull_unreachable(&mut build.map, start);
+    }
+}
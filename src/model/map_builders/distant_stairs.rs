@@ -0,0 +1,18 @@
+use bevy::log::warn;
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{farthest_from, MapBuild, MetaMapBuilder};
+
+/// Puts the stairs down on the reachable tile the longest walk from the start.
+#[derive(Debug, Clone, Copy)]
+pub struct DistantStairs;
+
+impl MetaMapBuilder for DistantStairs {
+    fn apply(&self, build: &mut MapBuild, _rng: &mut RandomNumberGenerator) {
+        let Some(start) = build.start else {
+            warn!("DistantStairs needs a start position, skipping it");
+            return;
+        };
+        build.down_stairs = Some(farthest_from(&build.map, start));
+    }
+}
@@ -0,0 +1,115 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{floor_count, in_interior, MapBuild, MapBuilder};
+use crate::model::components::{Position, TerrainType};
+use crate::model::resources::Map;
+use crate::model::systems::MoveDirection;
+
+/// Diffusion limited aggregation: particles wander in from random spots and stick to the
+/// first floor they bump into, growing branching caverns out of a small seed.
+///
+/// Particles start and wander close to what has grown so far, so they don't spend ages
+/// lost on big maps.
+#[derive(Debug, Clone)]
+pub struct DlaBuilder {
+    /// Percent of the map to clear
+    pub floor_percent: usize,
+}
+
+impl Default for DlaBuilder {
+    fn default() -> Self {
+        Self { floor_percent: 25 }
+    }
+}
+
+impl DlaBuilder {
+    /// How far past the aggregate particles may start and roam
+    const MARGIN: i32 = 3;
+}
+
+/// The part of the map particles stay in, the aggregate plus a margin
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    min: Position,
+    max: Position,
+}
+
+impl Bounds {
+    fn around(position: Position) -> Self {
+        Self {
+            min: position,
+            max: position,
+        }
+    }
+
+    fn grow(&mut self, position: Position) {
+        self.min = Position::new(self.min.x.min(position.x), self.min.y.min(position.y));
+        self.max = Position::new(self.max.x.max(position.x), self.max.y.max(position.y));
+    }
+
+    /// The bounds widened by `margin`, kept off the outer edge of `map`
+    fn widened(&self, margin: i32, map: &Map) -> Self {
+        Self {
+            min: Position::new((self.min.x - margin).max(1), (self.min.y - margin).max(1)),
+            max: Position::new(
+                (self.max.x + margin).min(map.width - 2),
+                (self.max.y + margin).min(map.height - 2),
+            ),
+        }
+    }
+
+    fn contains(&self, position: Position) -> bool {
+        (self.min.x..=self.max.x).contains(&position.x)
+            && (self.min.y..=self.max.y).contains(&position.y)
+    }
+}
+
+impl MapBuilder for DlaBuilder {
+    fn build(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator) {
+        let map = &mut build.map;
+        let start = Position::new(map.width / 2, map.height / 2);
+
+        // A small plus shaped seed for the particles to stick to
+        let mut bounds = Bounds::around(start);
+        for (dx, dy) in [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)] {
+            if in_interior(map, start.x + dx, start.y + dy) {
+                let idx = map.xy_idx(start.x + dx, start.y + dy);
+                map.tiles[idx] = TerrainType::Floor;
+                bounds.grow(Position::new(start.x + dx, start.y + dy));
+            }
+        }
+
+        let target = map.tiles.len() * self.floor_percent / 100;
+        let max_particles = map.tiles.len() * 4;
+        let mut floors = floor_count(map);
+        let mut particles = 0;
+        while floors < target && particles < max_particles {
+            particles += 1;
+
+            let area = bounds.widened(Self::MARGIN, map);
+            let mut particle = Position::new(
+                rng.range(area.min.x, area.max.x + 1),
+                rng.range(area.min.y, area.max.y + 1),
+            );
+            let mut previous = particle;
+
+            // Wander until hitting the aggregate, then settle on the last free tile
+            while *map.get_tile(particle.x, particle.y) == TerrainType::Wall {
+                previous = particle;
+                let direction =
+                    MoveDirection::CARDINALS[rng.range(0, MoveDirection::CARDINALS.len())];
+                let next = particle + direction.delta();
+                if area.contains(next) {
+                    particle = next;
+                }
+            }
+
+            let idx = map.xy_idx(previous.x, previous.y);
+            if map.tiles[idx] == TerrainType::Wall {
+                map.tiles[idx] = TerrainType::Floor;
+                floors += 1;
+                bounds.grow(previous);
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::log::warn;
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{MapBuild, MetaMapBuilder, Room};
+use crate::model::components::{Position, TerrainType};
+use crate::model::resources::Map;
+
+/// Hangs a door wherever a corridor breaks through the wall of a room.
+#[derive(Debug, Clone, Copy)]
+pub struct DoorPlacement;
+
+impl DoorPlacement {
+    /// The wall tiles around a room, without its corners
+    fn walls(room: &Room) -> Vec<Position> {
+        let horizontal = (room.x1 + 1..room.x2)
+            .flat_map(|x| [Position::new(x, room.y1), Position::new(x, room.y2)]);
+        let vertical = (room.y1 + 1..room.y2)
+            .flat_map(|y| [Position::new(room.x1, y), Position::new(room.x2, y)]);
+        horizontal.chain(vertical).collect()
+    }
+
+    /// A floor tile squeezed between two walls on opposite sides
+    fn is_doorway(map: &Map, pos: Position) -> bool {
+        let wall = |dx: i32, dy: i32| map.is_blocked(pos.x + dx, pos.y + dy);
+        let next_to_door = [(1, 0), (-1, 0), (0, 1), (0, -1)].iter().any(|(dx, dy)| {
+            map.in_bounds(pos.x + dx, pos.y + dy)
+                && *map.get_tile(pos.x + dx, pos.y + dy) == TerrainType::Door
+        });
+
+        *map.get_tile(pos.x, pos.y) == TerrainType::Floor
+            && !next_to_door
+            && ((wall(-1, 0) && wall(1, 0)) != (wall(0, -1) && wall(0, 1)))
+    }
+}
+
+impl MetaMapBuilder for DoorPlacement {
+    fn apply(&self, build: &mut MapBuild, _rng: &mut RandomNumberGenerator) {
+        let Some(rooms) = &build.rooms else {
+            warn!("DoorPlacement only works on levels with rooms, skipping it");
+            return;
+        };
+
+        let map = &mut build.map;
+        for room in rooms {
+            for pos in Self::walls(room) {
+                if map.in_bounds(pos.x, pos.y) && Self::is_doorway(map, pos) {
+                    let idx = map.xy_idx(pos.x, pos.y);
+                    map.tiles[idx] = TerrainType::Door;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{floor_count, in_interior, MapBuild, MapBuilder};
+use crate::model::components::{Position, TerrainType};
+use crate::model::systems::MoveDirection;
+
+/// Diggers stumble around at random, clearing everything they walk over.
+///
+/// Each new digger sets off from a floor tile that was already dug, so the level stays
+/// in one piece.
+#[derive(Debug, Clone)]
+pub struct DrunkardsWalkBuilder {
+    /// Steps a digger takes before it gives up
+    pub lifetime: i32,
+    /// Percent of the map to clear
+    pub floor_percent: usize,
+}
+
+impl Default for DrunkardsWalkBuilder {
+    fn default() -> Self {
+        Self {
+            lifetime: 400,
+            floor_percent: 50,
+        }
+    }
+}
+
+impl MapBuilder for DrunkardsWalkBuilder {
+    fn build(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator) {
+        let map = &mut build.map;
+        let start = Position::new(map.width / 2, map.height / 2);
+        let start_idx = map.xy_idx(start.x, start.y);
+        map.tiles[start_idx] = TerrainType::Floor;
+
+        let target = map.tiles.len() * self.floor_percent / 100;
+        let mut diggers = 0;
+        // Bounded in case the map is too small to ever reach the target
+        while floor_count(map) < target && diggers < 1000 {
+            diggers += 1;
+
+            let mut digger = if diggers == 1 {
+                start
+            } else {
+                let floors: Vec<usize> = map
+                    .tiles
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, tile)| **tile == TerrainType::Floor)
+                    .map(|(idx, _)| idx)
+                    .collect();
+                let (x, y) = map.idx_xy(floors[rng.range(0, floors.len())]);
+                Position::new(x, y)
+            };
+
+            for _ in 0..self.lifetime {
+                let idx = map.xy_idx(digger.x, digger.y);
+                map.tiles[idx] = TerrainType::Floor;
+
+                let direction =
+                    MoveDirection::CARDINALS[rng.range(0, MoveDirection::CARDINALS.len())];
+                let next = digger + direction.delta();
+                if in_interior(map, next.x, next.y) {
+                    digger = next;
+                }
+            }
+        }
+    }
+}
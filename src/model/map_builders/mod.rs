@@ -0,0 +1,278 @@
+use bevy::prelude::*;
+use bracket_lib::prelude::RandomNumberGenerator;
+use serde::{Deserialize, Serialize};
+
+use crate::model::components::{Position, TerrainType};
+use crate::model::resources::{LevelLayout, Map, PrefabLibrary};
+
+mod area_starting_position;
+mod bsp;
+mod cellular_automata;
+mod common;
+mod cull_unreachable;
+mod distant_stairs;
+mod dla;
+mod door_placement;
+mod drunkard;
+mod prefab;
+mod prefab_vaults;
+mod room_decorations;
+mod rooms_and_corridors;
+mod symmetry;
+mod wave_function_collapse;
+
+pub use area_starting_position::*;
+pub use bsp::*;
+pub use cellular_automata::*;
+pub use common::*;
+pub use cull_unreachable::*;
+pub use distant_stairs::*;
+pub use dla::*;
+pub use door_placement::*;
+pub use drunkard::*;
+pub use prefab::*;
+pub use prefab_vaults::*;
+pub use room_decorations::*;
+pub use rooms_and_corridors::*;
+pub use symmetry::*;
+pub use wave_function_collapse::*;
+
+/// A level on its way through a `BuilderChain`, before it is handed to the game.
+///
+/// Builders carve `map` and fill in whatever they know about the layout, anything left
+/// unset is worked out by `build_level`.
+#[derive(Debug, Clone)]
+pub struct MapBuild {
+    pub map: Map,
+    pub depth: i32,
+    /// Rooms of room based generators, caves and the like have none
+    pub rooms: Option<Vec<Room>>,
+    /// Where the player starts, the stairs back up go here below the surface
+    pub start: Option<Position>,
+    pub down_stairs: Option<Position>,
+    /// Groups of floor tiles monsters and items are spread across
+    pub spawn_regions: Vec<Vec<Position>>,
+    /// Monsters and items placed by prefabs
+    pub spawns: Vec<(Position, PrefabSpawn)>,
+}
+
+impl MapBuild {
+    /// A level of solid rock, ready to be carved.
+    ///
+    /// Diagonal squeezes count as blocked while building, so culling and stairs never rely on
+    /// a step the player may not be allowed to take. The game applies the real setting later.
+    pub fn new(width: i32, height: i32, depth: i32) -> Self {
+        let mut map = Map::new(width, height);
+        map.forbid_diagonal_squeeze = true;
+
+        Self {
+            map,
+            depth,
+            rooms: None,
+            start: None,
+            down_stairs: None,
+            spawn_regions: Vec::new(),
+            spawns: Vec::new(),
+        }
+    }
+}
+
+/// A map generation algorithm, carving a level out of solid rock
+pub trait MapBuilder {
+    fn build(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator);
+}
+
+/// A step that reworks a level some other builder has carved
+pub trait MetaMapBuilder {
+    fn apply(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator);
+}
+
+/// A generator followed by any number of meta steps, run in the order they were added.
+pub struct BuilderChain {
+    initial: Box<dyn MapBuilder>,
+    steps: Vec<Box<dyn MetaMapBuilder>>,
+}
+
+impl BuilderChain {
+    pub fn new(initial: Box<dyn MapBuilder>) -> Self {
+        Self {
+            initial,
+            steps: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with(mut self, step: impl MetaMapBuilder + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    pub fn build(
+        &self,
+        width: i32,
+        height: i32,
+        depth: i32,
+        rng: &mut RandomNumberGenerator,
+    ) -> MapBuild {
+        let mut build = MapBuild::new(width, height, depth);
+        self.initial.build(&mut build, rng);
+        for step in &self.steps {
+            step.apply(&mut build, rng);
+        }
+        build
+    }
+}
+
+/// The generators a level can be built with
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MapBuilderKind {
+    RoomsAndCorridors,
+    Bsp,
+    CellularAutomata,
+    DrunkardsWalk,
+    DiffusionLimitedAggregation,
+    WaveFunctionCollapse,
+}
+
+impl MapBuilderKind {
+    pub const ALL: [MapBuilderKind; 6] = [
+        MapBuilderKind::RoomsAndCorridors,
+        MapBuilderKind::Bsp,
+        MapBuilderKind::CellularAutomata,
+        MapBuilderKind::DrunkardsWalk,
+        MapBuilderKind::DiffusionLimitedAggregation,
+        MapBuilderKind::WaveFunctionCollapse,
+    ];
+
+    /// The first level is always rooms and corridors, deeper ones are a surprise
+    pub fn for_depth(depth: i32, rng: &mut RandomNumberGenerator) -> Self {
+        if depth <= 1 {
+            MapBuilderKind::RoomsAndCorridors
+        } else {
+            Self::ALL[rng.range(0, Self::ALL.len())]
+        }
+    }
+
+    /// The generator and the steps that finish its levels off.
+    ///
+    /// Room levels get decorated and doors, open ones are sometimes mirrored. Wave function
+    /// collapse learns from a sample prefab or another generator's level. Then the prefabs for
+    /// `depth` go wherever they fit, and every level starts near a corner, loses what can't be
+    /// reached and has its stairs far away.
+    pub fn chain(
+        &self,
+        depth: i32,
+        prefabs: &PrefabLibrary,
+        rng: &mut RandomNumberGenerator,
+    ) -> BuilderChain {
+        let initial: Box<dyn MapBuilder> = match self {
+            MapBuilderKind::WaveFunctionCollapse => Box::new(WaveFunctionCollapseBuilder::new(
+                Self::wfc_sample(depth, prefabs, rng),
+            )),
+            _ => self.builder(),
+        };
+        let chain = BuilderChain::new(initial);
+        let chain = match self {
+            MapBuilderKind::RoomsAndCorridors | MapBuilderKind::Bsp => {
+                chain.with(DoorPlacement).with(RoomDecorations)
+            }
+            MapBuilderKind::WaveFunctionCollapse => chain,
+            _ if rng.range(0, 3) == 0 => chain.with(Symmetry::random(rng)),
+            _ => chain,
+        };
+
+        chain
+            .with(PrefabVaults {
+                prefabs: prefabs.for_depth(depth),
+                max_vaults: 2,
+            })
+            .with(AreaStartingPosition::random_corner(rng))
+            .with(CullUnreachable)
+            .with(DistantStairs)
+    }
+
+    pub fn builder(&self) -> Box<dyn MapBuilder> {
+        match self {
+            MapBuilderKind::RoomsAndCorridors => Box::new(RoomsAndCorridorsBuilder::default()),
+            MapBuilderKind::Bsp => Box::new(BspBuilder::default()),
+            MapBuilderKind::CellularAutomata => Box::new(CellularAutomataBuilder::default()),
+            MapBuilderKind::DrunkardsWalk => Box::new(DrunkardsWalkBuilder::default()),
+            MapBuilderKind::DiffusionLimitedAggregation => Box::new(DlaBuilder::default()),
+            MapBuilderKind::WaveFunctionCollapse => {
+                Box::new(WaveFunctionCollapseBuilder::default())
+            }
+        }
+    }
+
+    /// Half the time one of the sample prefabs for `depth`, otherwise any other generator
+    fn wfc_sample(
+        depth: i32,
+        prefabs: &PrefabLibrary,
+        rng: &mut RandomNumberGenerator,
+    ) -> WfcSample {
+        let samples = prefabs.samples_for_depth(depth);
+        if !samples.is_empty() && rng.range(0, 2) == 0 {
+            return WfcSample::Prefab(samples[rng.range(0, samples.len())].clone());
+        }
+
+        let generators: Vec<MapBuilderKind> = Self::ALL
+            .into_iter()
+            .filter(|kind| *kind != MapBuilderKind::WaveFunctionCollapse)
+            .collect();
+        WfcSample::Generator(generators[rng.range(0, generators.len())])
+    }
+}
+
+/// Generates the level at `depth`, with `kind` or the one picked for the depth when unset.
+///
+/// Stairs are only turned into tiles here, after every step of the chain has run.
+pub fn build_level(
+    kind: Option<MapBuilderKind>,
+    width: i32,
+    height: i32,
+    depth: i32,
+    prefabs: &PrefabLibrary,
+    rng: &mut RandomNumberGenerator,
+) -> (Map, LevelLayout) {
+    let kind = kind.unwrap_or_else(|| MapBuilderKind::for_depth(depth, rng));
+    debug!("Building depth {depth} with {kind:?}");
+
+    let mut build = kind
+        .chain(depth, prefabs, rng)
+        .build(width, height, depth, rng);
+
+    // Chains without the usual finishing steps still get a playable level
+    let start = build
+        .start
+        .unwrap_or_else(|| nearest_floor(&build.map, Position::new(width / 2, height / 2)));
+    let down_stairs = build
+        .down_stairs
+        .unwrap_or_else(|| farthest_from(&build.map, start));
+    if build.spawn_regions.is_empty() {
+        build.spawn_regions = match &build.rooms {
+            Some(rooms) => room_regions(&build.map, rooms),
+            None => grid_regions(&build.map, REGION_SIZE),
+        };
+    }
+
+    let mut map = build.map;
+    let idx = map.xy_idx(down_stairs.x, down_stairs.y);
+    map.tiles[idx] = TerrainType::DownStairs;
+    if depth > 1 {
+        let idx = map.xy_idx(start.x, start.y);
+        map.tiles[idx] = TerrainType::UpStairs;
+    }
+
+    // Culling may have walled in part of a prefab
+    build
+        .spawns
+        .retain(|(pos, _)| !map.is_blocked(pos.x, pos.y) && *pos != start);
+
+    let layout = LevelLayout {
+        start,
+        spawn_regions: build.spawn_regions,
+        spawns: build.spawns,
+    };
+    (map, layout)
+}
@@ -0,0 +1,206 @@
+use bracket_lib::prelude::{to_char, XpFile};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{MapBuild, Room};
+use crate::model::components::{Position, TerrainType};
+use crate::model::resources::Map;
+use crate::model::systems::{ItemKind, MonsterKind};
+
+/// What one glyph of a prefab template stands for
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LegendEntry {
+    /// Monsters and items stand on floor unless told otherwise
+    #[serde(default)]
+    pub terrain: TerrainType,
+    #[serde(default)]
+    pub monster: Option<MonsterKind>,
+    #[serde(default)]
+    pub item: Option<ItemKind>,
+}
+
+impl LegendEntry {
+    fn terrain(terrain: TerrainType) -> Self {
+        Self {
+            terrain,
+            ..Self::default()
+        }
+    }
+
+    fn monster(monster: MonsterKind) -> Self {
+        Self {
+            monster: Some(monster),
+            ..Self::default()
+        }
+    }
+
+    fn item(item: ItemKind) -> Self {
+        Self {
+            item: Some(item),
+            ..Self::default()
+        }
+    }
+}
+
+/// Glyphs every template understands, the same ones the map and entities are drawn with.
+///
+/// A space leaves the level's own tile alone.
+pub fn default_legend() -> HashMap<char, LegendEntry> {
+    HashMap::from([
+        ('#', LegendEntry::terrain(TerrainType::Wall)),
+        ('.', LegendEntry::terrain(TerrainType::Floor)),
+        ('+', LegendEntry::terrain(TerrainType::Door)),
+        ('g', LegendEntry::monster(MonsterKind::Goblin)),
+        ('o', LegendEntry::monster(MonsterKind::Orc)),
+        ('!', LegendEntry::item(ItemKind::HealthPotion)),
+        ('%', LegendEntry::item(ItemKind::Ration)),
+        ('?', LegendEntry::item(ItemKind::FireballScroll)),
+        ('/', LegendEntry::item(ItemKind::Sword)),
+        ('[', LegendEntry::item(ItemKind::Shield)),
+    ])
+}
+
+/// Something a prefab puts on the level besides terrain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefabSpawn {
+    Monster(MonsterKind),
+    Item(ItemKind),
+}
+
+#[derive(Debug, Clone)]
+pub struct PrefabCell {
+    pub terrain: TerrainType,
+    pub spawn: Option<PrefabSpawn>,
+}
+
+/// A handmade room or vault, stamped into generated levels where there is room for it.
+#[derive(Debug, Clone)]
+pub struct Prefab {
+    pub name: String,
+    pub width: i32,
+    pub height: i32,
+    /// Row by row, `None` keeps whatever the level has there
+    pub cells: Vec<Option<PrefabCell>>,
+    pub min_depth: i32,
+    pub max_depth: i32,
+}
+
+impl Prefab {
+    /// Reads a plain text template, one line per row
+    pub fn from_text(
+        name: &str,
+        text: &str,
+        legend: &HashMap<char, LegendEntry>,
+    ) -> Result<Self, String> {
+        let rows: Vec<Vec<char>> = text
+            .lines()
+            .map(|line| line.trim_end_matches('\r').chars().collect())
+            .collect();
+        Self::from_glyphs(name, rows, legend)
+    }
+
+    /// Reads the first layer of a REXPaint image, glyphs are looked up by their cp437 code
+    pub fn from_xp(
+        name: &str,
+        xp: &XpFile,
+        legend: &HashMap<char, LegendEntry>,
+    ) -> Result<Self, String> {
+        let layer = xp
+            .layers
+            .first()
+            .ok_or_else(|| format!("{name} has no layers"))?;
+        let rows = (0..layer.height)
+            .map(|y| {
+                (0..layer.width)
+                    .map(|x| match layer.get(x, y) {
+                        Some(cell) if cell.ch != 0 => to_char(cell.ch as u8),
+                        _ => ' ',
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::from_glyphs(name, rows, legend)
+    }
+
+    fn from_glyphs(
+        name: &str,
+        rows: Vec<Vec<char>>,
+        legend: &HashMap<char, LegendEntry>,
+    ) -> Result<Self, String> {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if width == 0 {
+            return Err(format!("{name} is empty"));
+        }
+
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for row in &rows {
+            for x in 0..width {
+                let glyph = row.get(x).copied().unwrap_or(' ');
+                if glyph == ' ' {
+                    cells.push(None);
+                    continue;
+                }
+
+                let entry = legend
+                    .get(&glyph)
+                    .ok_or_else(|| format!("{name} uses '{glyph}', which isn't in the legend"))?;
+                let spawn = match (entry.monster, entry.item) {
+                    (Some(monster), _) => Some(PrefabSpawn::Monster(monster)),
+                    (None, Some(item)) => Some(PrefabSpawn::Item(item)),
+                    (None, None) => None,
+                };
+                cells.push(Some(PrefabCell {
+                    terrain: entry.terrain.clone(),
+                    spawn,
+                }));
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            width: width as i32,
+            height: rows.len() as i32,
+            cells,
+            min_depth: 1,
+            max_depth: i32::MAX,
+        })
+    }
+
+    pub const fn suits_depth(&self, depth: i32) -> bool {
+        depth >= self.min_depth && depth <= self.max_depth
+    }
+
+    /// Whether the prefab fits at `at` on open floor, with a ring of floor left around it
+    /// so the rest of the level stays connected
+    pub fn fits(&self, map: &Map, at: Position) -> bool {
+        (at.y - 1..=at.y + self.height).all(|y| {
+            (at.x - 1..=at.x + self.width).all(|x| {
+                x > 0
+                    && y > 0
+                    && x < map.width - 1
+                    && y < map.height - 1
+                    && *map.get_tile(x, y) == TerrainType::Floor
+            })
+        })
+    }
+
+    /// Copies the prefab onto the level with its top left corner at `at`
+    pub fn stamp(&self, build: &mut MapBuild, at: Position) {
+        for (i, cell) in self.cells.iter().enumerate() {
+            let Some(cell) = cell else {
+                continue;
+            };
+            let pos = Position::new(at.x + i as i32 % self.width, at.y + i as i32 / self.width);
+            let idx = build.map.xy_idx(pos.x, pos.y);
+            build.map.tiles[idx] = cell.terrain.clone();
+            if let Some(spawn) = cell.spawn {
+                build.spawns.push((pos, spawn));
+            }
+        }
+    }
+
+    /// The area the prefab covers at `at`
+    pub fn footprint(&self, at: Position) -> Room {
+        Room::new(at.x, at.y, self.width - 1, self.height - 1)
+    }
+}
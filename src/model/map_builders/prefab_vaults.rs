@@ -0,0 +1,54 @@
+use bevy::log::debug;
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{MapBuild, MetaMapBuilder, Prefab, Room};
+use crate::model::components::Position;
+
+/// Stamps up to `max_vaults` prefabs into open parts of the level.
+///
+/// Levels without room for any of them are left as they are.
+#[derive(Debug, Clone)]
+pub struct PrefabVaults {
+    pub prefabs: Vec<Prefab>,
+    pub max_vaults: i32,
+}
+
+impl PrefabVaults {
+    /// Spots tried for each vault before giving up on it
+    const ATTEMPTS: i32 = 50;
+}
+
+impl MetaMapBuilder for PrefabVaults {
+    fn apply(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator) {
+        if self.prefabs.is_empty() {
+            return;
+        }
+
+        let mut placed: Vec<Room> = Vec::new();
+        for _ in 0..self.max_vaults {
+            let prefab = &self.prefabs[rng.range(0, self.prefabs.len())];
+            let (max_x, max_y) = (
+                build.map.width - prefab.width - 1,
+                build.map.height - prefab.height - 1,
+            );
+            if max_x <= 1 || max_y <= 1 {
+                continue;
+            }
+
+            for _ in 0..Self::ATTEMPTS {
+                let at = Position::new(rng.range(1, max_x), rng.range(1, max_y));
+                let footprint = prefab.footprint(at);
+                if placed.iter().any(|other| footprint.intersect(other))
+                    || !prefab.fits(&build.map, at)
+                {
+                    continue;
+                }
+
+                debug!("Placing {} at {:?}", prefab.name, at);
+                prefab.stamp(build, at);
+                placed.push(footprint);
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use bevy::log::warn;
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{MapBuild, MetaMapBuilder, Room};
+use crate::model::components::{Position, TerrainType};
+use crate::model::resources::Map;
+
+/// Dresses up large rooms with pillars in their corners or a colonnade along their edges.
+///
+/// Pillars never go next to an opening in the wall, so no entrance gets blocked.
+#[derive(Debug, Clone, Copy)]
+pub struct RoomDecorations;
+
+impl RoomDecorations {
+    /// Rooms need this many floor tiles across for corner pillars, twice for colonnades
+    const MIN_INTERIOR: i32 = 5;
+
+    fn corners(room: &Room) -> Vec<Position> {
+        vec![
+            Position::new(room.x1 + 1, room.y1 + 1),
+            Position::new(room.x2 - 1, room.y1 + 1),
+            Position::new(room.x1 + 1, room.y2 - 1),
+            Position::new(room.x2 - 1, room.y2 - 1),
+        ]
+    }
+
+    /// Every other tile one step in from the walls
+    fn colonnade(room: &Room) -> Vec<Position> {
+        let horizontal = (room.x1 + 2..room.x2 - 1)
+            .step_by(2)
+            .flat_map(|x| [Position::new(x, room.y1 + 2), Position::new(x, room.y2 - 2)]);
+        let vertical = (room.y1 + 2..room.y2 - 1)
+            .step_by(2)
+            .flat_map(|y| [Position::new(room.x1 + 2, y), Position::new(room.x2 - 2, y)]);
+        horizontal.chain(vertical).collect()
+    }
+
+    /// Only floor tiles whose neighbours are all room floor or solid room wall
+    fn can_place(map: &Map, room: &Room, pos: Position) -> bool {
+        let on_wall = |x: i32, y: i32| x == room.x1 || x == room.x2 || y == room.y1 || y == room.y2;
+
+        *map.get_tile(pos.x, pos.y) == TerrainType::Floor
+            && (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (pos.x + dx, pos.y + dy)))
+                .all(|(x, y)| !on_wall(x, y) || *map.get_tile(x, y) == TerrainType::Wall)
+    }
+}
+
+impl MetaMapBuilder for RoomDecorations {
+    fn apply(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator) {
+        let Some(rooms) = &build.rooms else {
+            warn!("RoomDecorations only works on levels with rooms, skipping it");
+            return;
+        };
+
+        let map = &mut build.map;
+        for room in rooms {
+            let interior = (room.width() - 1).min(room.height() - 1);
+            let pillars = match rng.range(0, 3) {
+                0 if interior >= Self::MIN_INTERIOR * 2 => Self::colonnade(room),
+                1 if interior >= Self::MIN_INTERIOR => Self::corners(room),
+                _ => continue,
+            };
+
+            for pos in pillars {
+                if Self::can_place(map, room, pos) {
+                    let idx = map.xy_idx(pos.x, pos.y);
+                    map.tiles[idx] = TerrainType::Wall;
+                }
+            }
+        }
+    }
+}
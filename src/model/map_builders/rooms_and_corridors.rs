@@ -0,0 +1,54 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{apply_l_tunnel, apply_room_to_map, MapBuild, MapBuilder, Room};
+
+/// Randomly placed rooms, each joined to the one before it by a corridor.
+#[derive(Debug, Clone)]
+pub struct RoomsAndCorridorsBuilder {
+    /// Placement attempts on a default sized map, most of them overlap and are dropped
+    pub max_rooms: i32,
+    pub min_size: i32,
+    pub max_size: i32,
+}
+
+impl Default for RoomsAndCorridorsBuilder {
+    fn default() -> Self {
+        Self {
+            max_rooms: 30,
+            min_size: 6,
+            max_size: 10,
+        }
+    }
+}
+
+impl MapBuilder for RoomsAndCorridorsBuilder {
+    fn build(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator) {
+        let map = &mut build.map;
+        let max_rooms = (self.max_rooms as f32 * map.size_factor()).round() as i32;
+        let mut rooms: Vec<Room> = Vec::new();
+
+        for _ in 0..max_rooms {
+            let w = rng.range(self.min_size, self.max_size);
+            let h = rng.range(self.min_size, self.max_size);
+            // No room for a room this size on a small map
+            if w + 2 >= map.width || h + 2 >= map.height {
+                continue;
+            }
+            let x = rng.range(1, map.width - w - 1);
+            let y = rng.range(1, map.height - h - 1);
+            let new_room = Room::new(x, y, w, h);
+
+            if rooms.iter().any(|other| new_room.intersect(other)) {
+                continue;
+            }
+
+            apply_room_to_map(map, &new_room);
+            if let Some(prev) = rooms.last() {
+                apply_l_tunnel(map, prev.center(), new_room.center(), rng.range(0, 2) == 1);
+            }
+            rooms.push(new_room);
+        }
+
+        build.rooms = Some(rooms);
+    }
+}
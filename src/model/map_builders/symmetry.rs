@@ -0,0 +1,99 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use super::{MapBuild, MetaMapBuilder, Room};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymmetryAxis {
+    /// The left half is mirrored onto the right
+    Horizontal,
+    /// The top half is mirrored onto the bottom
+    Vertical,
+    Both,
+}
+
+/// Mirrors one half of the level onto the other.
+///
+/// Rooms cut by the axis are forgotten, so room based steps should run before this one.
+#[derive(Debug, Clone, Copy)]
+pub struct Symmetry {
+    pub axis: SymmetryAxis,
+}
+
+impl Symmetry {
+    pub fn random(rng: &mut RandomNumberGenerator) -> Self {
+        let axis = match rng.range(0, 3) {
+            0 => SymmetryAxis::Horizontal,
+            1 => SymmetryAxis::Vertical,
+            _ => SymmetryAxis::Both,
+        };
+        Self { axis }
+    }
+
+    fn mirror_x(build: &mut MapBuild) {
+        let map = &mut build.map;
+        let width = map.width;
+        for y in 0..map.height {
+            for x in 0..width / 2 {
+                let from = map.xy_idx(x, y);
+                let to = map.xy_idx(width - 1 - x, y);
+                map.tiles[to] = map.tiles[from].clone();
+            }
+        }
+
+        if let Some(rooms) = &mut build.rooms {
+            rooms.retain(|room| room.x2 < width / 2);
+            let mirrored: Vec<Room> = rooms
+                .iter()
+                .map(|room| Room {
+                    x1: width - 1 - room.x2,
+                    x2: width - 1 - room.x1,
+                    ..room.clone()
+                })
+                .collect();
+            rooms.extend(mirrored);
+        }
+    }
+
+    fn mirror_y(build: &mut MapBuild) {
+        let map = &mut build.map;
+        let height = map.height;
+        for y in 0..height / 2 {
+            for x in 0..map.width {
+                let from = map.xy_idx(x, y);
+                let to = map.xy_idx(x, height - 1 - y);
+                map.tiles[to] = map.tiles[from].clone();
+            }
+        }
+
+        if let Some(rooms) = &mut build.rooms {
+            rooms.retain(|room| room.y2 < height / 2);
+            let mirrored: Vec<Room> = rooms
+                .iter()
+                .map(|room| Room {
+                    y1: height - 1 - room.y2,
+                    y2: height - 1 - room.y1,
+                    ..room.clone()
+                })
+                .collect();
+            rooms.extend(mirrored);
+        }
+    }
+}
+
+impl MetaMapBuilder for Symmetry {
+    fn apply(&self, build: &mut MapBuild, _rng: &mut RandomNumberGenerator) {
+        match self.axis {
+            SymmetryAxis::Horizontal => Self::mirror_x(build),
+            SymmetryAxis::Vertical => Self::mirror_y(build),
+            SymmetryAxis::Both => {
+                Self::mirror_x(build);
+                Self::mirror_y(build);
+            }
+        }
+
+        // Whatever was worked out about the old layout no longer holds
+        build.start = None;
+        build.down_stairs = None;
+        build.spawn_regions.clear();
+    }
+}
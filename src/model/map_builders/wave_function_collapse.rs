@@ -0,0 +1,333 @@
+use bevy::log::warn;
+use bracket_lib::prelude::RandomNumberGenerator;
+use std::collections::HashMap;
+
+use super::{
+    connect_regions, floor_count, CellularAutomataBuilder, MapBuild, MapBuilder, MapBuilderKind,
+    Prefab,
+};
+use crate::model::components::TerrainType;
+use crate::model::resources::Map;
+
+/// What wave function collapse learns its patterns from
+#[derive(Debug, Clone)]
+pub enum WfcSample {
+    /// A level of the same size made by another generator
+    Generator(MapBuilderKind),
+    /// A handmade template, its monsters and items are ignored
+    Prefab(Prefab),
+}
+
+/// Wave function collapse: cuts a sample level into small chunks, learns which chunks may sit
+/// next to each other and pieces together a new level of any size from them.
+///
+/// Chunks are placed where the fewest options are left and their neighbours narrowed down,
+/// starting over when a spot runs out of options. The pieces are joined up by tunnels
+/// afterwards, so the level is always in one piece.
+#[derive(Debug, Clone)]
+pub struct WaveFunctionCollapseBuilder {
+    pub sample: WfcSample,
+    /// Side of the square chunks, in tiles
+    pub chunk_size: i32,
+}
+
+impl Default for WaveFunctionCollapseBuilder {
+    fn default() -> Self {
+        Self::new(WfcSample::Generator(MapBuilderKind::CellularAutomata))
+    }
+}
+
+impl WaveFunctionCollapseBuilder {
+    /// Fresh starts allowed before falling back to plain caves
+    const ATTEMPTS: i32 = 10;
+
+    pub const fn new(sample: WfcSample) -> Self {
+        Self {
+            sample,
+            chunk_size: 3,
+        }
+    }
+
+    fn sample_map(&self, build: &MapBuild, rng: &mut RandomNumberGenerator) -> Map {
+        match &self.sample {
+            WfcSample::Generator(kind) => {
+                let mut sample = MapBuild::new(build.map.width, build.map.height, build.depth);
+                kind.builder().build(&mut sample, rng);
+                sample.map
+            }
+            WfcSample::Prefab(prefab) => {
+                let mut map = Map::new(prefab.width, prefab.height);
+                for (tile, cell) in map.tiles.iter_mut().zip(&prefab.cells) {
+                    if let Some(cell) = cell {
+                        *tile = cell.terrain.clone();
+                    }
+                }
+                map
+            }
+        }
+    }
+}
+
+/// Direction from a chunk to its neighbour, indexes `Patterns::compatible`
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Every distinct chunk of the sample and which of them may sit side by side
+struct Patterns {
+    tiles: Vec<Vec<TerrainType>>,
+    /// How often each chunk shows up in the sample
+    weights: Vec<u32>,
+    /// `compatible[direction][a]`: the chunks that may sit in `direction` of `a`
+    compatible: [Vec<Vec<usize>>; 4],
+}
+
+impl Patterns {
+    fn learn(sample: &Map, size: i32) -> Self {
+        let mut index: HashMap<Vec<TerrainType>, usize> = HashMap::new();
+        let mut tiles = Vec::new();
+        let mut weights = Vec::new();
+
+        for y in 0..=sample.height - size {
+            for x in 0..=sample.width - size {
+                let chunk: Vec<TerrainType> = (0..size)
+                    .flat_map(|dy| (0..size).map(move |dx| (x + dx, y + dy)))
+                    .map(|(x, y)| sample.get_tile(x, y).clone())
+                    .collect();
+                let id = *index.entry(chunk.clone()).or_insert_with(|| {
+                    tiles.push(chunk);
+                    weights.push(0);
+                    tiles.len() - 1
+                });
+                weights[id] += 1;
+            }
+        }
+
+        // Two chunks fit when the facing edges match, so chunks are grouped by each edge once
+        let edges: Vec<[Vec<TerrainType>; 4]> = tiles
+            .iter()
+            .map(|chunk| DIRECTIONS.map(|direction| Self::edge(chunk, size, direction)))
+            .collect();
+        let compatible = std::array::from_fn(|direction| {
+            let opposite = (direction + 2) % DIRECTIONS.len();
+            let mut by_edge: HashMap<&[TerrainType], Vec<usize>> = HashMap::new();
+            for (b, edge) in edges.iter().enumerate() {
+                by_edge
+                    .entry(edge[opposite].as_slice())
+                    .or_default()
+                    .push(b);
+            }
+            edges
+                .iter()
+                .map(|edge| {
+                    by_edge
+                        .get(edge[direction].as_slice())
+                        .cloned()
+                        .unwrap_or_default()
+                })
+                .collect()
+        });
+
+        Self {
+            tiles,
+            weights,
+            compatible,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// The row or column of a chunk facing `direction`
+    fn edge(chunk: &[TerrainType], size: i32, direction: (i32, i32)) -> Vec<TerrainType> {
+        let last = size - 1;
+        (0..size)
+            .map(|i| match direction {
+                (0, -1) => (i, 0),
+                (1, 0) => (last, i),
+                (0, 1) => (i, last),
+                _ => (0, i),
+            })
+            .map(|(x, y)| chunk[(y * size + x) as usize].clone())
+            .collect()
+    }
+}
+
+/// The chunks still possible at each spot of the output grid
+struct Wave<'a> {
+    patterns: &'a Patterns,
+    width: i32,
+    height: i32,
+    options: Vec<Vec<usize>>,
+}
+
+impl<'a> Wave<'a> {
+    fn new(patterns: &'a Patterns, width: i32, height: i32) -> Self {
+        Self {
+            patterns,
+            width,
+            height,
+            options: vec![(0..patterns.len()).collect(); (width * height) as usize],
+        }
+    }
+
+    /// Collapses the whole grid, `false` when some spot ran out of options
+    fn solve(&mut self, rng: &mut RandomNumberGenerator) -> bool {
+        loop {
+            // The undecided spot with the fewest options left, ties broken at random
+            let Some(fewest) = self
+                .options
+                .iter()
+                .map(Vec::len)
+                .filter(|len| *len > 1)
+                .min()
+            else {
+                return true;
+            };
+            let candidates: Vec<usize> = (0..self.options.len())
+                .filter(|i| self.options[*i].len() == fewest)
+                .collect();
+            let cell = candidates[rng.range(0, candidates.len())];
+
+            let choice = self.weighted_choice(cell, rng);
+            self.options[cell] = vec![choice];
+            if !self.propagate(cell) {
+                return false;
+            }
+        }
+    }
+
+    fn weighted_choice(&self, cell: usize, rng: &mut RandomNumberGenerator) -> usize {
+        let options = &self.options[cell];
+        let total: u32 = options.iter().map(|p| self.patterns.weights[*p]).sum();
+        let mut roll = rng.range(0, total);
+        for pattern in options {
+            let weight = self.patterns.weights[*pattern];
+            if roll < weight {
+                return *pattern;
+            }
+            roll -= weight;
+        }
+        options[0]
+    }
+
+    /// Narrows down the neighbours of `cell` until nothing changes, `false` on a dead end
+    fn propagate(&mut self, cell: usize) -> bool {
+        // Chunks some option of the current spot allows, cleared after every neighbour
+        let mut allowed = vec![false; self.patterns.len()];
+        let mut stack = vec![cell];
+        while let Some(current) = stack.pop() {
+            let (x, y) = (current as i32 % self.width, current as i32 / self.width);
+            let current_options = self.options[current].clone();
+            for (direction, (dx, dy)) in DIRECTIONS.iter().enumerate() {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < 0 || ny < 0 || nx >= self.width || ny >= self.height {
+                    continue;
+                }
+
+                let neighbour = (ny * self.width + nx) as usize;
+                let compatible = &self.patterns.compatible[direction];
+                for b in current_options.iter().flat_map(|a| &compatible[*a]) {
+                    allowed[*b] = true;
+                }
+                let before = self.options[neighbour].len();
+                self.options[neighbour].retain(|b| allowed[*b]);
+                allowed.fill(false);
+
+                match self.options[neighbour].len() {
+                    0 => return false,
+                    len if len < before => stack.push(neighbour),
+                    _ => {}
+                }
+            }
+        }
+        true
+    }
+}
+
+impl MapBuilder for WaveFunctionCollapseBuilder {
+    fn build(&self, build: &mut MapBuild, rng: &mut RandomNumberGenerator) {
+        let sample = self.sample_map(build, rng);
+        let size = self.chunk_size;
+        let map = &mut build.map;
+
+        if sample.width >= size && sample.height >= size {
+            let patterns = Patterns::learn(&sample, size);
+            let (width, height) = (map.width / size, map.height / size);
+
+            for _ in 0..Self::ATTEMPTS {
+                let mut wave = Wave::new(&patterns, width, height);
+                if !wave.solve(rng) {
+                    continue;
+                }
+
+                for (i, options) in wave.options.iter().enumerate() {
+                    let (cx, cy) = (i as i32 % width, i as i32 / width);
+                    for (j, tile) in patterns.tiles[options[0]].iter().enumerate() {
+                        let x = cx * size + j as i32 % size;
+                        let y = cy * size + j as i32 / size;
+                        let idx = map.xy_idx(x, y);
+                        map.tiles[idx] = tile.clone();
+                    }
+                }
+                break;
+            }
+        }
+
+        // The outer edge always stays wall
+        for y in 0..map.height {
+            for x in 0..map.width {
+                if x == 0 || y == 0 || x == map.width - 1 || y == map.height - 1 {
+                    let idx = map.xy_idx(x, y);
+                    map.tiles[idx] = TerrainType::Wall;
+                }
+            }
+        }
+
+        if floor_count(map) == 0 {
+            warn!("Wave function collapse found no level, falling back to caves");
+            CellularAutomataBuilder::default().build(build, rng);
+        }
+        connect_regions(&mut build.map);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::components::Position;
+    use crate::model::map_builders::{default_legend, distances_from, nearest_floor};
+
+    const SAMPLE: &str = "\
+##########
+#....#...#
+#.##.#.#.#
+#.#......#
+#.#.####.#
+#........#
+##########";
+
+    #[test]
+    fn small_sample_makes_a_connected_level() {
+        let prefab = Prefab::from_text("sample", SAMPLE, &default_legend()).unwrap();
+        let builder = WaveFunctionCollapseBuilder::new(WfcSample::Prefab(prefab));
+
+        for seed in 0..5 {
+            let mut rng = RandomNumberGenerator::seeded(seed);
+            let mut build = MapBuild::new(40, 25, 1);
+            builder.build(&mut build, &mut rng);
+            let map = &build.map;
+
+            assert!(floor_count(map) > 0, "seed {seed} made no floor");
+
+            let start = nearest_floor(map, Position::new(map.width / 2, map.height / 2));
+            let distances = distances_from(map, start);
+            let unreachable = map
+                .tiles
+                .iter()
+                .zip(&distances)
+                .filter(|(tile, distance)| tile.is_walkable() && **distance == f32::MAX)
+                .count();
+            assert_eq!(unreachable, 0, "seed {seed} left tiles cut off");
+        }
+    }
+}
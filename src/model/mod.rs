@@ -1,5 +1,7 @@
 pub mod commands;
 pub mod components;
+pub mod events;
+pub mod map_builders;
 pub mod resources;
 pub mod systems;
 
@@ -1,6 +1,13 @@
 pub struct ModelConstants;
 
 impl ModelConstants {
+    /// Default level size, room and spawn counts are tuned for it
     pub const MAP_WIDTH: u32 = 80;
     pub const MAP_HEIGHT: u32 = 50;
+
+    /// Energy spent by a single action
+    pub const ACTION_COST: i32 = 10;
+
+    /// Number of items an actor can carry, one per letter
+    pub const BACKPACK_CAPACITY: usize = 26;
 }
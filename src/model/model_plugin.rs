@@ -1,10 +1,13 @@
 use bevy::prelude::*;
 
 use crate::model::components::*;
+use crate::model::events::*;
 use crate::model::resources::*;
 use crate::model::systems::*;
 use crate::AppSet;
+use crate::AppSettings;
 use crate::RunningState;
+use crate::TurnPhase;
 
 pub struct ModelPlugin;
 impl Plugin for ModelPlugin {
@@ -12,36 +15,148 @@ impl Plugin for ModelPlugin {
         // Register components
         app.register_type::<Position>()
             .register_type::<MapLevel>()
+            .register_type::<StashedPosition>()
             .register_type::<Renderable>()
             .register_type::<crate::model::components::Name>()
             .register_type::<Player>()
             .register_type::<Monster>()
             .register_type::<NPC>()
             .register_type::<Stats>()
+            .register_type::<EffectiveStats>()
+            .register_type::<Energy>()
+            .register_type::<Ai>()
             .register_type::<FieldOfView>()
             .register_type::<Item>()
             .register_type::<Consumable>()
             .register_type::<Equippable>()
             .register_type::<InBackpack>()
-            .register_type::<Equipped>();
+            .register_type::<Equipped>()
+            .register_type::<StatBonus>()
+            .register_type::<ProvidesHealing>()
+            .register_type::<InflictsDamage>()
+            .register_type::<InflictsStatus>()
+            .register_type::<Ranged>()
+            .register_type::<AreaOfEffect>()
+            .register_type::<StatusEffects>()
+            .register_type::<Description>();
+
+        // Register resources that are saved with the run
+        app.register_type::<Map>()
+            .register_type::<TurnState>()
+            .register_type::<GameLog>()
+            .register_type::<Dungeon>();
+
+        // Register events
+        app.add_event::<AttackIntent>()
+            .add_event::<DamageDealt>()
+            .add_event::<Death>()
+            .add_event::<ItemUsed>()
+            .add_event::<ChangeLevel>();
 
         // Register resources
-        app.init_resource::<Map>()
+        let seed = app
+            .world()
+            .get_resource::<AppSettings>()
+            .and_then(AppSettings::seed);
+        app.insert_resource(GameRng::new(seed))
+            .init_resource::<Map>()
+            .init_resource::<Dungeon>()
             .init_resource::<TurnState>()
-            .init_resource::<GameLog>();
+            .init_resource::<GameLog>()
+            .init_resource::<LevelLayout>()
+            .init_resource::<StatusClock>()
+            .insert_resource(PrefabLibrary::load())
+            .init_resource::<SaveStatus>();
 
         // Register systems
         app.add_systems(OnEnter(RunningState::Load), setup_game)
             .add_systems(Update, generate_map.run_if(in_state(RunningState::Load)))
+            // Only a freshly generated dungeon gets populated, loaded runs bring their own
             .add_systems(
-                OnEnter(RunningState::Running),
-                (spawn_initial_player, spawn_monsters, spawn_items),
+                OnExit(RunningState::Load),
+                (
+                    spawn_initial_player,
+                    spawn_monsters,
+                    spawn_items,
+                    spawn_prefab_contents,
+                )
+                    .chain(),
             )
+            .add_systems(OnEnter(RunningState::GameOver), discard_save)
+            .add_systems(OnEnter(RunningState::Paused), refund_player_turn)
+            .add_systems(Last, save_on_exit)
             .add_systems(
                 Update,
                 update_fov
                     .in_set(AppSet::Visibility)
                     .run_if(in_state(RunningState::Running)),
+            )
+            .add_systems(
+                Update,
+                update_effective_stats
+                    .in_set(AppSet::Update)
+                    .before(schedule_turns)
+                    .before(resolve_attacks)
+                    .run_if(in_state(RunningState::Running)),
+            )
+            .add_systems(
+                Update,
+                change_level
+                    .in_set(AppSet::Update)
+                    .before(schedule_turns)
+                    .run_if(in_state(RunningState::Running)),
+            )
+            .add_systems(
+                Update,
+                sync_movement_rules
+                    .in_set(AppSet::Update)
+                    .after(change_level)
+                    .before(schedule_turns),
+            )
+            .add_systems(
+                Update,
+                (schedule_turns, monster_ai)
+                    .chain()
+                    .in_set(AppSet::Update)
+                    .run_if(in_state(TurnPhase::MonsterTurn)),
+            )
+            .add_systems(
+                Update,
+                sync_log_turn
+                    .in_set(AppSet::Update)
+                    .after(schedule_turns)
+                    .before(monster_ai)
+                    .before(tick_status_effects)
+                    .run_if(in_state(RunningState::Running)),
+            )
+            .add_systems(
+                Update,
+                (resolve_attacks, handle_deaths)
+                    .chain()
+                    .in_set(AppSet::Update)
+                    .after(monster_ai)
+                    .run_if(in_state(RunningState::Running)),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_healing,
+                    apply_item_damage,
+                    apply_item_status,
+                    consume_used_items,
+                )
+                    .chain()
+                    .in_set(AppSet::Update)
+                    .before(handle_deaths)
+                    .run_if(in_state(RunningState::Running)),
+            )
+            .add_systems(
+                Update,
+                tick_status_effects
+                    .in_set(AppSet::Update)
+                    .after(schedule_turns)
+                    .before(handle_deaths)
+                    .run_if(in_state(RunningState::Running)),
             );
     }
 }
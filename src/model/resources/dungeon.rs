@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::model::resources::Map;
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct Dungeon {
+    /// Depth of the level the player is on, starting at 1
+    pub depth: i32,
+    /// Maps of the levels the player has visited but is not currently on
+    pub stashed_levels: HashMap<i32, Map>,
+}
+
+impl Default for Dungeon {
+    fn default() -> Self {
+        Self {
+            depth: 1,
+            stashed_levels: HashMap::new(),
+        }
+    }
+}
@@ -1,60 +1,136 @@
 use bevy::prelude::*;
 use std::collections::VecDeque;
 
-use crate::ui::UiConstants;
-
 const DEFAULT_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
 
+/// What a log message is about, the history screen filters on this
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum LogCategory {
+    Combat,
+    Movement,
+    Items,
+    Status,
+    System,
+}
+
+impl LogCategory {
+    pub const ALL: [LogCategory; 5] = [
+        LogCategory::Combat,
+        LogCategory::Movement,
+        LogCategory::Items,
+        LogCategory::Status,
+        LogCategory::System,
+    ];
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            LogCategory::Combat => "Combat",
+            LogCategory::Movement => "Movement",
+            LogCategory::Items => "Items",
+            LogCategory::Status => "Status",
+            LogCategory::System => "System",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Reflect)]
 pub struct LogEntry {
     pub text: String,
     pub color: Color,
+    pub category: LogCategory,
+    /// Turn of the latest repeat of this message
+    pub turn: u32,
+    /// How many times in a row the message was logged
+    pub count: u32,
 }
 
 impl LogEntry {
-    pub fn new(text: impl Into<String>, color: Color) -> Self {
+    pub fn new(category: LogCategory, text: impl Into<String>, color: Color, turn: u32) -> Self {
         Self {
             text: text.into(),
             color,
+            category,
+            turn,
+            count: 1,
+        }
+    }
+
+    /// The message with repeats collapsed into a counter, e.g. "You move. x3"
+    pub fn display_text(&self) -> String {
+        if self.count > 1 {
+            format!("{} x{}", self.text, self.count)
+        } else {
+            self.text.clone()
         }
     }
 }
 
+/// The messages of the current run up to `GameLog::MAX_ENTRIES`, the HUD shows only the newest
 #[derive(Resource, Debug, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct GameLog {
     pub entries: VecDeque<LogEntry>,
+    /// World turn new entries are stamped with, kept in sync with `TurnState`
+    pub turn: u32,
 }
 
 impl Default for GameLog {
     fn default() -> Self {
         let mut log = Self {
-            entries: VecDeque::with_capacity(UiConstants::LOG_LIMIT),
+            entries: VecDeque::new(),
+            turn: 0,
         };
-        log.add_entry_srgb("Welcome to the dungeon!", DEFAULT_COLOR);
+        log.add_entry_srgb(
+            LogCategory::System,
+            "Welcome to the dungeon!",
+            DEFAULT_COLOR,
+        );
         log
     }
 }
 
 impl GameLog {
+    /// Older entries are dropped past this, the log is saved with the run
+    pub const MAX_ENTRIES: usize = 1000;
+
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub fn add_entry(&mut self, text: impl Into<String>) {
-        self.entries.push_back(LogEntry::new(text, DEFAULT_COLOR));
-        if self.entries.len() > UiConstants::LOG_LIMIT {
-            self.entries.pop_front();
-        }
+    pub fn add_entry(&mut self, category: LogCategory, text: impl Into<String>) {
+        self.add_entry_srgb(category, text, DEFAULT_COLOR);
     }
 
-    pub fn add_entry_srgb(&mut self, text: impl Into<String>, color: Color) {
-        self.entries.push_back(LogEntry::new(text, color));
-        if self.entries.len() > UiConstants::LOG_LIMIT {
+    pub fn add_entry_srgb(&mut self, category: LogCategory, text: impl Into<String>, color: Color) {
+        let text = text.into();
+
+        // Collapse a message repeated back to back into a single counted entry
+        if let Some(last) = self.entries.back_mut() {
+            if last.text == text && last.category == category {
+                last.count += 1;
+                last.turn = self.turn;
+                last.color = color;
+                return;
+            }
+        }
+
+        self.entries
+            .push_back(LogEntry::new(category, text, color, self.turn));
+        while self.entries.len() > Self::MAX_ENTRIES {
             self.entries.pop_front();
         }
     }
 
+    /// Entries of the given categories, oldest first
+    pub fn filtered<'a>(
+        &'a self,
+        categories: &'a [LogCategory],
+    ) -> impl DoubleEndedIterator<Item = &'a LogEntry> + 'a {
+        self.entries
+            .iter()
+            .filter(|entry| categories.contains(&entry.category))
+    }
+
     pub fn clear(&mut self) {
         self.entries.clear();
     }
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use crate::model::components::Position;
+use crate::model::map_builders::PrefabSpawn;
+
+/// What the map generator worked out about the current level beyond its tiles.
+///
+/// Only needed while the level is populated, so it isn't saved with the run.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LevelLayout {
+    /// Where the player starts
+    pub start: Position,
+    /// Groups of floor tiles monsters and items are spread across
+    pub spawn_regions: Vec<Vec<Position>>,
+    /// Monsters and items placed by prefabs
+    pub spawns: Vec<(Position, PrefabSpawn)>,
+}
+
+impl LevelLayout {
+    /// A floor tile from a random region, so spawns spread evenly over the level
+    pub fn random_spawn_tile(&self, rng: &mut RandomNumberGenerator) -> Option<Position> {
+        if self.spawn_regions.is_empty() {
+            return None;
+        }
+        let region = &self.spawn_regions[rng.range(0, self.spawn_regions.len())];
+        if region.is_empty() {
+            return None;
+        }
+        Some(region[rng.range(0, region.len())])
+    }
+}
@@ -12,6 +12,9 @@ pub struct Map {
     pub tiles: Vec<TerrainType>,
     pub revealed_tiles: Vec<bool>,
     pub visible_tiles: Vec<bool>,
+    /// Whether diagonal steps between two walls are blocked, set from `AppSettings`
+    #[reflect(ignore)]
+    pub forbid_diagonal_squeeze: bool,
 }
 
 impl Default for Map {
@@ -26,6 +29,7 @@ impl Default for Map {
             height,
             revealed_tiles: vec![false; size],
             visible_tiles: vec![false; size],
+            forbid_diagonal_squeeze: false,
         };
 
         // Create a simple room in the middle
@@ -50,6 +54,7 @@ impl Map {
             height,
             revealed_tiles: vec![false; size],
             visible_tiles: vec![false; size],
+            forbid_diagonal_squeeze: false,
         }
     }
 
@@ -80,6 +85,38 @@ impl Map {
         !self.tiles[idx].is_walkable()
     }
 
+    /// A diagonal step from `from` that would pass between two walls, when those are forbidden
+    pub fn is_squeeze(&self, from: Position, (dx, dy): (i32, i32)) -> bool {
+        self.forbid_diagonal_squeeze
+            && dx != 0
+            && dy != 0
+            && self.is_blocked(from.x + dx, from.y)
+            && self.is_blocked(from.x, from.y + dy)
+    }
+
+    /// Whether an actor at `from` can step by `delta`, ignoring other actors
+    pub fn can_step(&self, from: Position, delta: (i32, i32)) -> bool {
+        let to = from + delta;
+        !self.is_blocked(to.x, to.y) && !self.is_squeeze(from, delta)
+    }
+
+    /// Area relative to a default sized map, used to scale room and spawn counts
+    pub fn size_factor(&self) -> f32 {
+        (self.width * self.height) as f32
+            / (ModelConstants::MAP_WIDTH * ModelConstants::MAP_HEIGHT) as f32
+    }
+
+    /// The first tile of the given terrain, scanning row by row
+    pub fn find_terrain(&self, terrain: &TerrainType) -> Option<Position> {
+        self.tiles
+            .iter()
+            .position(|tile| tile == terrain)
+            .map(|idx| {
+                let (x, y) = self.idx_xy(idx);
+                Position::new(x, y)
+            })
+    }
+
     pub fn clear_visibility(&mut self) {
         for visible in &mut self.visible_tiles {
             *visible = false;
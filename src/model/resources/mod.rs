@@ -1,7 +1,19 @@
+mod dungeon;
 mod game_log;
+mod level_layout;
 mod map;
+mod prefab_library;
+mod rng;
+mod save_status;
+mod status_clock;
 mod turn_state;
 
+pub use dungeon::*;
 pub use game_log::*;
+pub use level_layout::*;
 pub use map::*;
+pub use prefab_library::*;
+pub use rng::*;
+pub use save_status::*;
+pub use status_clock::*;
 pub use turn_state::*;
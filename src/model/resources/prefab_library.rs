@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+use bracket_lib::prelude::XpFile;
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use crate::model::map_builders::{default_legend, LegendEntry, Prefab};
+use crate::AppConstants;
+
+/// One entry of `prefabs.toml`
+#[derive(Debug, Deserialize)]
+struct PrefabEntry {
+    name: String,
+    /// Template next to `prefabs.toml`, `.xp` files are read as REXPaint images
+    file: String,
+    #[serde(default = "PrefabEntry::default_min_depth")]
+    min_depth: i32,
+    #[serde(default = "PrefabEntry::default_max_depth")]
+    max_depth: i32,
+    /// Glyphs only this prefab uses
+    #[serde(default)]
+    legend: HashMap<char, LegendEntry>,
+}
+
+impl PrefabEntry {
+    const fn default_min_depth() -> i32 {
+        1
+    }
+
+    const fn default_max_depth() -> i32 {
+        i32::MAX
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PrefabFile {
+    /// Glyphs added to or replacing the built in legend for every prefab
+    #[serde(default)]
+    legend: HashMap<char, LegendEntry>,
+    #[serde(default)]
+    prefab: Vec<PrefabEntry>,
+    /// Templates wave function collapse learns from, never stamped as they are
+    #[serde(default)]
+    sample: Vec<PrefabEntry>,
+}
+
+/// Handmade rooms and vaults from `assets/prefabs`, stamped into levels as they are built.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PrefabLibrary {
+    pub prefabs: Vec<Prefab>,
+    pub samples: Vec<Prefab>,
+}
+
+impl PrefabLibrary {
+    const PATH: &str = "prefabs";
+    const FILE_NAME: &str = "prefabs.toml";
+
+    fn folder() -> PathBuf {
+        let mut folder = PathBuf::from(AppConstants::BASE);
+        folder.push(Self::PATH);
+        folder
+    }
+
+    /// Reads every prefab and sample listed in `prefabs.toml`.
+    ///
+    /// Prefabs that can't be read are printed and left out, the rest still load.
+    pub fn load() -> Self {
+        let file_path = Self::folder().join(Self::FILE_NAME);
+        let contents = match fs::read_to_string(&file_path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                warn!("No prefabs loaded from {}: {err}", file_path.display());
+                return Self::default();
+            }
+        };
+        let file: PrefabFile = match toml::from_str(&contents) {
+            Ok(file) => file,
+            Err(err) => {
+                warn!("Failed to parse {}: {err}", file_path.display());
+                return Self::default();
+            }
+        };
+
+        let mut shared_legend = default_legend();
+        shared_legend.extend(file.legend);
+
+        Self {
+            prefabs: Self::read_all(file.prefab, &shared_legend),
+            samples: Self::read_all(file.sample, &shared_legend),
+        }
+    }
+
+    fn read_all(
+        entries: Vec<PrefabEntry>,
+        shared_legend: &HashMap<char, LegendEntry>,
+    ) -> Vec<Prefab> {
+        entries
+            .into_iter()
+            .filter_map(|entry| {
+                let mut legend = shared_legend.clone();
+                legend.extend(entry.legend.clone());
+                match Self::read_prefab(&entry, &legend) {
+                    Ok(prefab) => Some(prefab),
+                    Err(err) => {
+                        warn!("Skipping prefab {}: {err}", entry.name);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn read_prefab(
+        entry: &PrefabEntry,
+        legend: &HashMap<char, LegendEntry>,
+    ) -> Result<Prefab, String> {
+        let path = Self::folder().join(&entry.file);
+        let mut prefab = if path.extension().is_some_and(|ext| ext == "xp") {
+            let mut file = fs::File::open(&path).map_err(|err| err.to_string())?;
+            let xp = XpFile::read(&mut file).map_err(|err| format!("{err:?}"))?;
+            Prefab::from_xp(&entry.name, &xp, legend)?
+        } else {
+            let text = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+            Prefab::from_text(&entry.name, &text, legend)?
+        };
+
+        prefab.min_depth = entry.min_depth;
+        prefab.max_depth = entry.max_depth;
+        Ok(prefab)
+    }
+
+    /// Prefabs allowed on the level at `depth`
+    pub fn for_depth(&self, depth: i32) -> Vec<Prefab> {
+        self.prefabs
+            .iter()
+            .filter(|prefab| prefab.suits_depth(depth))
+            .cloned()
+            .collect()
+    }
+
+    /// Wave function collapse samples allowed on the level at `depth`
+    pub fn samples_for_depth(&self, depth: i32) -> Vec<Prefab> {
+        self.samples
+            .iter()
+            .filter(|prefab| prefab.suits_depth(depth))
+            .cloned()
+            .collect()
+    }
+}
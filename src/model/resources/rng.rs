@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+use bracket_lib::prelude::RandomNumberGenerator;
+
+use crate::model::resources::TurnState;
+
+/// Every random decision of a run, split into streams derived from a single seed.
+///
+/// Keeping the streams apart means a few extra combat rolls don't change which dungeon
+/// the next level turns out to be. Each stream is reseeded from the seed and where the run
+/// is, the level for `map` and `spawn` and the turn for `combat`, so a loaded run rolls
+/// exactly what it would have without the save.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    /// Seed requested by the settings or command line, `None` rolls a new one each run
+    fixed_seed: Option<u64>,
+    pub map: RandomNumberGenerator,
+    pub spawn: RandomNumberGenerator,
+    pub combat: RandomNumberGenerator,
+}
+
+impl GameRng {
+    const MAP_STREAM: u64 = 1;
+    const SPAWN_STREAM: u64 = 2;
+    const COMBAT_STREAM: u64 = 3;
+    /// Monsters roll on `combat` too, reseeded apart from the player's rolls
+    const MONSTER_STREAM: u64 = 4;
+
+    pub fn new(fixed_seed: Option<u64>) -> Self {
+        let seed = fixed_seed.unwrap_or_else(|| RandomNumberGenerator::new().next_u64());
+        Self {
+            seed,
+            fixed_seed,
+            map: Self::stream(seed, Self::MAP_STREAM, 0),
+            spawn: Self::stream(seed, Self::SPAWN_STREAM, 0),
+            combat: Self::stream(seed, Self::COMBAT_STREAM, 0),
+        }
+    }
+
+    /// The seed of the current run
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts the streams over for a new run
+    pub fn reset(&mut self) {
+        *self = Self::new(self.fixed_seed);
+    }
+
+    /// Picks a saved run back up, every stream is reseeded before it is next used
+    pub fn resume(&mut self, seed: u64) {
+        let fixed_seed = self.fixed_seed;
+        *self = Self::new(Some(seed));
+        self.fixed_seed = fixed_seed;
+    }
+
+    /// Reseeds `map` and `spawn` for generating the level at `depth`
+    pub fn enter_level(&mut self, depth: i32) {
+        self.map = Self::stream(self.seed, Self::MAP_STREAM, depth as u64);
+        self.spawn = Self::stream(self.seed, Self::SPAWN_STREAM, depth as u64);
+    }
+
+    /// Reseeds `combat` for the player's next action, several can share a turn
+    pub fn start_player_action(&mut self, turn_state: &TurnState) {
+        let key = (u64::from(turn_state.turn_number) << 32)
+            | u64::from(turn_state.action_points.unsigned_abs());
+        self.combat = Self::stream(self.seed, Self::COMBAT_STREAM, key);
+    }
+
+    /// Reseeds `combat` for the monsters acting this turn
+    pub fn start_monster_turn(&mut self, turn_state: &TurnState) {
+        let key = u64::from(turn_state.turn_number);
+        self.combat = Self::stream(self.seed, Self::MONSTER_STREAM, key);
+    }
+
+    /// Mixes the run seed with a stream id and key (splitmix64) so nearby seeds don't correlate
+    fn stream(seed: u64, stream: u64, key: u64) -> RandomNumberGenerator {
+        let mut z = seed
+            .wrapping_add(stream.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .wrapping_add(key.wrapping_mul(0xD1B5_4A32_D192_ED03));
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        RandomNumberGenerator::seeded(z ^ (z >> 31))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rolls(rng: &mut RandomNumberGenerator) -> Vec<u64> {
+        (0..8).map(|_| rng.next_u64()).collect()
+    }
+
+    /// Rolls from every stream at one point of a run
+    fn streams(rng: &mut GameRng, depth: i32, turn_state: &TurnState) -> Vec<Vec<u64>> {
+        rng.enter_level(depth);
+        let (map, spawn) = (rolls(&mut rng.map), rolls(&mut rng.spawn));
+        rng.start_player_action(turn_state);
+        let player = rolls(&mut rng.combat);
+        rng.start_monster_turn(turn_state);
+        let monsters = rolls(&mut rng.combat);
+        vec![map, spawn, player, monsters]
+    }
+
+    fn turn(turn_number: u32, action_points: i32) -> TurnState {
+        TurnState {
+            current_entity: None,
+            action_points,
+            turn_number,
+        }
+    }
+
+    #[test]
+    fn a_fixed_seed_reproduces_every_stream() {
+        let turn_state = turn(12, 100);
+        let expected = streams(&mut GameRng::new(Some(42)), 3, &turn_state);
+
+        // Whatever was rolled before, the same point of the run rolls the same
+        let mut rng = GameRng::new(Some(42));
+        streams(&mut rng, 1, &turn(2, 50));
+        assert_eq!(streams(&mut rng, 3, &turn_state), expected);
+
+        let mut resumed = GameRng::new(None);
+        resumed.resume(42);
+        assert_eq!(streams(&mut resumed, 3, &turn_state), expected);
+    }
+
+    #[test]
+    fn streams_differ_by_seed_level_and_turn() {
+        let turn_state = turn(12, 100);
+        let base = streams(&mut GameRng::new(Some(42)), 3, &turn_state);
+
+        let other_seed = streams(&mut GameRng::new(Some(43)), 3, &turn_state);
+        assert!(base.iter().zip(&other_seed).all(|(a, b)| a != b));
+
+        let deeper = streams(&mut GameRng::new(Some(42)), 4, &turn_state);
+        assert_ne!(base[0], deeper[0]);
+        assert_ne!(base[1], deeper[1]);
+
+        let later = streams(&mut GameRng::new(Some(42)), 3, &turn(13, 100));
+        assert_ne!(base[2], later[2]);
+        assert_ne!(base[3], later[3]);
+
+        // The player and the monsters never share rolls
+        assert_ne!(base[2], base[3]);
+    }
+}
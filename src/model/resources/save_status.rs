@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// Outcome of the last save or load, shown on the start menu
+#[derive(Resource, Debug, Default)]
+pub struct SaveStatus {
+    pub message: Option<String>,
+}
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+/// The world turn statuses were last counted down on.
+///
+/// Not saved, a new or loaded run sets it to its own turn so the first tick doesn't count
+/// the turns of whatever ran before.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct StatusClock {
+    pub last_tick: u32,
+}
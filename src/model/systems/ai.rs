@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use bracket_lib::prelude::{a_star_search, DijkstraMap, RandomNumberGenerator};
+use std::collections::HashSet;
+
+use crate::model::components::{
+    Ai, Behavior, Energy, FieldOfView, Monster, Player, Position, Stats, StatusEffects, StatusKind,
+};
+use crate::model::events::AttackIntent;
+use crate::model::resources::{GameRng, Map, TurnState};
+use crate::model::systems::MoveDirection;
+use crate::model::ModelConstants;
+
+/// How far a fleeing monster looks ahead when picking an escape route
+const FLEE_DEPTH: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AiAction {
+    Move(Position),
+    Attack(Entity),
+    Wait,
+}
+
+/// Everything a behaviour needs to know about the world for one decision
+struct AiContext<'a> {
+    map: &'a Map,
+    position: Position,
+    player_entity: Option<Entity>,
+    player_position: Option<Position>,
+    sees_player: bool,
+    occupied: &'a HashSet<Position>,
+}
+
+/// Lets every monster that can afford an action spend it according to its behaviours.
+pub fn monster_ai(
+    map: Res<Map>,
+    turn_state: Res<TurnState>,
+    mut rng: ResMut<GameRng>,
+    mut attack_events: EventWriter<AttackIntent>,
+    player_query: Query<(Entity, &Position), With<Player>>,
+    mut monster_query: Query<
+        (
+            Entity,
+            &mut Ai,
+            &Stats,
+            &mut Energy,
+            &mut Position,
+            &mut FieldOfView,
+            &StatusEffects,
+        ),
+        (With<Monster>, Without<Player>),
+    >,
+) {
+    // Only reseed when someone acts, the system runs every frame of the monsters' turn
+    if monster_query
+        .iter()
+        .any(|(_, _, _, energy, _, _, _)| energy.0 >= ModelConstants::ACTION_COST)
+    {
+        rng.start_monster_turn(&turn_state);
+    }
+    let rng = &mut rng.combat;
+    let player = player_query.get_single().ok();
+    let player_entity = player.map(|(entity, _)| entity);
+    let player_position = player.map(|(_, position)| *position);
+
+    let mut occupied: HashSet<Position> = monster_query
+        .iter()
+        .map(|(_, _, _, _, position, _, _)| *position)
+        .chain(player_position)
+        .collect();
+
+    // Built lazily, only when someone actually needs to run away
+    let mut flee_map: Option<DijkstraMap> = None;
+
+    for (entity, mut ai, stats, mut energy, mut position, mut fov, effects) in
+        monster_query.iter_mut()
+    {
+        while energy.0 >= ModelConstants::ACTION_COST {
+            energy.0 -= ModelConstants::ACTION_COST;
+
+            let sees_player = player_position.is_some_and(|player| fov.is_visible(&player));
+            if sees_player {
+                ai.last_seen_player = player_position;
+            }
+
+            let ctx = AiContext {
+                map: &map,
+                position: *position,
+                player_entity,
+                player_position,
+                sees_player,
+                occupied: &occupied,
+            };
+
+            // Confusion overrides whatever the monster would rather do
+            let action = if effects.has(StatusKind::Confused) {
+                wander(&ctx, 100, rng)
+            } else {
+                ai.behaviors.iter().find_map(|behavior| match *behavior {
+                    Behavior::Flee { health_fraction } => {
+                        flee(&ctx, stats, health_fraction, &mut flee_map)
+                    }
+                    Behavior::Chase => chase(&ctx, ai.last_seen_player),
+                    Behavior::Wander { chance } => wander(&ctx, chance, rng),
+                })
+            }
+            .unwrap_or(AiAction::Wait);
+
+            match action {
+                AiAction::Move(target) => {
+                    occupied.remove(&*position);
+                    occupied.insert(target);
+                    *position = target;
+                    fov.is_dirty = true;
+
+                    // Lost track of the player once we reach where they were
+                    if ai.last_seen_player == Some(target) && !sees_player {
+                        ai.last_seen_player = None;
+                    }
+                }
+                AiAction::Attack(target) => {
+                    attack_events.send(AttackIntent {
+                        attacker: entity,
+                        target,
+                    });
+                }
+                AiAction::Wait => {}
+            }
+        }
+    }
+}
+
+fn flee(
+    ctx: &AiContext,
+    stats: &Stats,
+    health_fraction: f32,
+    flee_map: &mut Option<DijkstraMap>,
+) -> Option<AiAction> {
+    let player_position = ctx.player_position?;
+    if !ctx.sees_player || stats.health as f32 >= stats.max_health as f32 * health_fraction {
+        return None;
+    }
+
+    let map = ctx.map;
+    let flee_map = flee_map.get_or_insert_with(|| {
+        let start = map.xy_idx(player_position.x, player_position.y);
+        DijkstraMap::new(map.width, map.height, &[start], map, FLEE_DEPTH)
+    });
+
+    let idx = map.xy_idx(ctx.position.x, ctx.position.y);
+    let exit = DijkstraMap::find_highest_exit(flee_map, idx, map)?;
+    let (x, y) = map.idx_xy(exit);
+    let target = Position::new(x, y);
+
+    // Cornered, let the next behaviour decide
+    if ctx.occupied.contains(&target) {
+        return None;
+    }
+
+    Some(AiAction::Move(target))
+}
+
+fn chase(ctx: &AiContext, last_seen_player: Option<Position>) -> Option<AiAction> {
+    let target = if ctx.sees_player {
+        ctx.player_position?
+    } else {
+        last_seen_player?
+    };
+
+    if target == ctx.position {
+        return None;
+    }
+
+    let map = ctx.map;
+    let path = a_star_search(
+        map.xy_idx(ctx.position.x, ctx.position.y),
+        map.xy_idx(target.x, target.y),
+        map,
+    );
+    if !path.success || path.steps.len() < 2 {
+        return None;
+    }
+
+    let (x, y) = map.idx_xy(path.steps[1]);
+    let next = Position::new(x, y);
+
+    if Some(next) == ctx.player_position {
+        return ctx.player_entity.map(AiAction::Attack);
+    }
+
+    // Stuck behind another monster, hold position
+    if ctx.occupied.contains(&next) {
+        return Some(AiAction::Wait);
+    }
+
+    Some(AiAction::Move(next))
+}
+
+fn wander(ctx: &AiContext, chance: i32, rng: &mut RandomNumberGenerator) -> Option<AiAction> {
+    if rng.range(0, 100) >= chance {
+        return None;
+    }
+
+    let direction = rng.random_slice_entry(&MoveDirection::ALL)?;
+    let target = ctx.position + direction.delta();
+    if !ctx.map.can_step(ctx.position, direction.delta()) || ctx.occupied.contains(&target) {
+        return None;
+    }
+
+    Some(AiAction::Move(target))
+}
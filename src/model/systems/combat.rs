@@ -0,0 +1,149 @@
+use crate::model::components::{
+    Ai, EffectiveStats, Energy, FieldOfView, Monster, Name, Player, Renderable, Stats,
+};
+use crate::model::events::{AttackIntent, DamageDealt, Death};
+use crate::model::resources::{GameLog, GameRng, LogCategory};
+use crate::RunningState;
+use bevy::prelude::*;
+
+/// An attack lands when `d20 + attack` beats this plus the target's defense
+const HIT_THRESHOLD: i32 = 10;
+/// A natural roll of this much always hits and deals double damage
+const CRITICAL_ROLL: i32 = 20;
+
+const HIT_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
+const MISS_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+const DEATH_COLOR: Color = Color::srgb(1.0, 0.0, 0.0);
+
+/// Rolls to hit and applies damage for every attack declared this frame.
+pub fn resolve_attacks(
+    mut attack_events: EventReader<AttackIntent>,
+    mut damage_events: EventWriter<DamageDealt>,
+    mut death_events: EventWriter<Death>,
+    mut game_log: ResMut<GameLog>,
+    mut rng: ResMut<GameRng>,
+    mut stats_query: Query<(&mut Stats, &EffectiveStats)>,
+    name_query: Query<(&Name, Has<Player>)>,
+) {
+    let rng = &mut rng.combat;
+
+    for attack in attack_events.read() {
+        let Ok([(attacker_stats, attacker_effective), (mut target_stats, target_effective)]) =
+            stats_query.get_many_mut([attack.attacker, attack.target])
+        else {
+            continue;
+        };
+
+        // The dead don't fight back
+        if attacker_stats.health <= 0 || target_stats.health <= 0 {
+            continue;
+        }
+
+        let (attacker, attacker_is_player) = display_name(&name_query, attack.attacker);
+        let attacker = capitalize(&attacker);
+        let (target, _) = display_name(&name_query, attack.target);
+
+        let roll = rng.roll_dice(1, 20);
+        let critical = roll >= CRITICAL_ROLL;
+        if !critical && roll + attacker_effective.attack <= HIT_THRESHOLD + target_effective.defense
+        {
+            let verb = if attacker_is_player { "miss" } else { "misses" };
+            game_log.add_entry_srgb(
+                LogCategory::Combat,
+                format!("{attacker} {verb} {target}."),
+                MISS_COLOR,
+            );
+            continue;
+        }
+
+        let mut damage = (rng.roll_dice(1, attacker_effective.attack.max(1))
+            - target_effective.defense / 2)
+            .max(1);
+        if critical {
+            damage *= 2;
+        }
+        target_stats.health -= damage;
+
+        let verb = match (attacker_is_player, critical) {
+            (true, true) => "critically hit",
+            (true, false) => "hit",
+            (false, true) => "critically hits",
+            (false, false) => "hits",
+        };
+        game_log.add_entry_srgb(
+            LogCategory::Combat,
+            format!("{attacker} {verb} {target} for {damage} damage."),
+            HIT_COLOR,
+        );
+
+        damage_events.send(DamageDealt {
+            source: Some(attack.attacker),
+            target: attack.target,
+            amount: damage,
+        });
+
+        if target_stats.health <= 0 {
+            death_events.send(Death {
+                entity: attack.target,
+                killer: Some(attack.attacker),
+            });
+        }
+    }
+}
+
+/// Turns dead monsters into corpses and ends the game when the player dies.
+pub fn handle_deaths(
+    mut commands: Commands,
+    mut death_events: EventReader<Death>,
+    mut game_log: ResMut<GameLog>,
+    mut next_state: ResMut<NextState<RunningState>>,
+    mut victim_query: Query<(&Name, &mut Renderable, Has<Player>)>,
+) {
+    for death in death_events.read() {
+        let Ok((name, mut renderable, is_player)) = victim_query.get_mut(death.entity) else {
+            continue;
+        };
+
+        if is_player {
+            game_log.add_entry_srgb(LogCategory::Combat, "You die...", DEATH_COLOR);
+            next_state.set(RunningState::GameOver);
+            continue;
+        }
+
+        game_log.add_entry_srgb(
+            LogCategory::Combat,
+            format!("The {} dies.", name.0),
+            DEATH_COLOR,
+        );
+
+        // Corpses lie beneath everything else
+        renderable.glyph = '%';
+        renderable.fg = Color::srgb(0.5, 0.0, 0.0);
+        renderable.render_order = -1;
+
+        commands
+            .entity(death.entity)
+            .remove::<(Monster, Ai, Stats, Energy, FieldOfView)>()
+            .insert(Name(format!("{} corpse", name.0)));
+    }
+}
+
+/// How an entity is referred to in the log, and whether it is the player
+pub(crate) fn display_name(
+    name_query: &Query<(&Name, Has<Player>)>,
+    entity: Entity,
+) -> (String, bool) {
+    match name_query.get(entity) {
+        Ok((_, true)) => ("you".to_string(), true),
+        Ok((name, false)) => (format!("the {}", name.0), false),
+        Err(_) => ("something".to_string(), false),
+    }
+}
+
+pub(crate) fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,221 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bracket_lib::prelude::{field_of_view, Point};
+use std::collections::HashSet;
+
+use crate::model::components::{
+    AreaOfEffect, Consumable, InflictsDamage, InflictsStatus, Name, Player, Position,
+    ProvidesHealing, Stats, StatusEffects, StatusKind,
+};
+use crate::model::events::{DamageDealt, Death, ItemUsed};
+use crate::model::resources::{GameLog, LogCategory, Map, StatusClock, TurnState};
+use crate::model::systems::{capitalize, display_name};
+
+const HEAL_COLOR: Color = Color::srgb(0.0, 1.0, 0.0);
+const DAMAGE_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
+const STATUS_COLOR: Color = Color::srgb(0.8, 0.0, 0.8);
+
+/// Works out which actors an item use affects.
+#[derive(SystemParam)]
+pub struct EffectTargets<'w, 's> {
+    map: Res<'w, Map>,
+    actor_query: Query<'w, 's, (Entity, &'static Position), With<Stats>>,
+    area_query: Query<'w, 's, &'static AreaOfEffect>,
+}
+
+impl EffectTargets<'_, '_> {
+    /// The user themselves, unless the item was aimed at a tile
+    pub fn resolve(&self, used: &ItemUsed) -> Vec<Entity> {
+        let Some(target) = used.target else {
+            return vec![used.user];
+        };
+
+        let tiles: HashSet<Position> = match self.area_query.get(used.item) {
+            Ok(area) => field_of_view(Point::new(target.x, target.y), area.radius, &*self.map)
+                .into_iter()
+                .map(|point| Position::new(point.x, point.y))
+                .collect(),
+            Err(_) => HashSet::from([target]),
+        };
+
+        self.actor_query
+            .iter()
+            .filter(|(_, position)| tiles.contains(position))
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+}
+
+pub fn apply_healing(
+    mut used_events: EventReader<ItemUsed>,
+    targets: EffectTargets,
+    healing_query: Query<&ProvidesHealing>,
+    mut stats_query: Query<&mut Stats>,
+    name_query: Query<(&Name, Has<Player>)>,
+    mut game_log: ResMut<GameLog>,
+) {
+    for used in used_events.read() {
+        let Ok(healing) = healing_query.get(used.item) else {
+            continue;
+        };
+
+        for target in targets.resolve(used) {
+            let Ok(mut stats) = stats_query.get_mut(target) else {
+                continue;
+            };
+            stats.health = (stats.health + healing.amount).min(stats.max_health);
+
+            let (name, is_player) = display_name(&name_query, target);
+            let verb = if is_player { "feel" } else { "looks" };
+            game_log.add_entry_srgb(
+                LogCategory::Items,
+                format!("{} {verb} better.", capitalize(&name)),
+                HEAL_COLOR,
+            );
+        }
+    }
+}
+
+pub fn apply_item_damage(
+    mut used_events: EventReader<ItemUsed>,
+    mut damage_events: EventWriter<DamageDealt>,
+    mut death_events: EventWriter<Death>,
+    targets: EffectTargets,
+    damage_query: Query<&InflictsDamage>,
+    mut stats_query: Query<&mut Stats>,
+    name_query: Query<(&Name, Has<Player>)>,
+    mut game_log: ResMut<GameLog>,
+) {
+    for used in used_events.read() {
+        let Ok(damage) = damage_query.get(used.item) else {
+            continue;
+        };
+
+        for target in targets.resolve(used) {
+            let Ok(mut stats) = stats_query.get_mut(target) else {
+                continue;
+            };
+            if stats.health <= 0 {
+                continue;
+            }
+            stats.health -= damage.amount;
+
+            let (name, is_player) = display_name(&name_query, target);
+            let verb = if is_player { "take" } else { "takes" };
+            game_log.add_entry_srgb(
+                LogCategory::Combat,
+                format!("{} {verb} {} damage.", capitalize(&name), damage.amount),
+                DAMAGE_COLOR,
+            );
+
+            damage_events.send(DamageDealt {
+                source: Some(used.user),
+                target,
+                amount: damage.amount,
+            });
+
+            if stats.health <= 0 {
+                death_events.send(Death {
+                    entity: target,
+                    killer: Some(used.user),
+                });
+            }
+        }
+    }
+}
+
+pub fn apply_item_status(
+    mut used_events: EventReader<ItemUsed>,
+    targets: EffectTargets,
+    status_query: Query<&InflictsStatus>,
+    mut effects_query: Query<&mut StatusEffects>,
+    name_query: Query<(&Name, Has<Player>)>,
+    mut game_log: ResMut<GameLog>,
+) {
+    for used in used_events.read() {
+        let Ok(status) = status_query.get(used.item) else {
+            continue;
+        };
+
+        for target in targets.resolve(used) {
+            let Ok(mut effects) = effects_query.get_mut(target) else {
+                continue;
+            };
+            effects.add(status.kind, status.duration);
+
+            let (name, is_player) = display_name(&name_query, target);
+            let verb = if is_player { "are" } else { "is" };
+            game_log.add_entry_srgb(
+                LogCategory::Status,
+                format!("{} {verb} {}!", capitalize(&name), status.kind.adjective()),
+                STATUS_COLOR,
+            );
+        }
+    }
+}
+
+/// Consumables are used up once every effect has been applied.
+pub fn consume_used_items(
+    mut commands: Commands,
+    mut used_events: EventReader<ItemUsed>,
+    consumable_query: Query<(), With<Consumable>>,
+) {
+    for used in used_events.read() {
+        if consumable_query.contains(used.item) {
+            commands.entity(used.item).despawn_recursive();
+        }
+    }
+}
+
+/// Counts statuses down as world turns pass and applies their per-turn effects.
+pub fn tick_status_effects(
+    turn_state: Res<TurnState>,
+    mut clock: ResMut<StatusClock>,
+    mut damage_events: EventWriter<DamageDealt>,
+    mut death_events: EventWriter<Death>,
+    mut game_log: ResMut<GameLog>,
+    mut actor_query: Query<(Entity, &mut StatusEffects, &mut Stats, Has<Player>), With<Position>>,
+) {
+    let elapsed = turn_state.turn_number.saturating_sub(clock.last_tick) as i32;
+    if clock.last_tick != turn_state.turn_number {
+        clock.last_tick = turn_state.turn_number;
+    }
+    if elapsed == 0 {
+        return;
+    }
+
+    for (entity, mut effects, mut stats, is_player) in actor_query.iter_mut() {
+        if effects.remaining.is_empty() || stats.health <= 0 {
+            continue;
+        }
+
+        for (kind, turns) in effects.remaining.iter_mut() {
+            let active_for = elapsed.min(*turns);
+            *turns -= active_for;
+
+            if *kind == StatusKind::Poisoned {
+                stats.health -= active_for;
+                damage_events.send(DamageDealt {
+                    source: None,
+                    target: entity,
+                    amount: active_for,
+                });
+            }
+
+            if *turns <= 0 && is_player {
+                game_log.add_entry_srgb(
+                    LogCategory::Status,
+                    format!("You are no longer {}.", kind.adjective()),
+                    STATUS_COLOR,
+                );
+            }
+        }
+        effects.remaining.retain(|(_, turns)| *turns > 0);
+
+        if stats.health <= 0 {
+            death_events.send(Death {
+                entity,
+                killer: None,
+            });
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::model::components::{EffectiveStats, Equipped, StatBonus, Stats};
+
+/// Recomputes every actor's `EffectiveStats` from its base `Stats` and equipped items.
+pub fn update_effective_stats(
+    mut actor_query: Query<(Entity, &Stats, &mut EffectiveStats)>,
+    equipped_query: Query<(&Equipped, &StatBonus)>,
+) {
+    let mut bonuses: HashMap<Entity, StatBonus> = HashMap::new();
+    for (equipped, bonus) in equipped_query.iter() {
+        let total = bonuses.entry(equipped.owner).or_default();
+        total.attack += bonus.attack;
+        total.defense += bonus.defense;
+        total.speed += bonus.speed;
+    }
+
+    for (entity, stats, mut effective) in actor_query.iter_mut() {
+        let bonus = bonuses.get(&entity).copied().unwrap_or_default();
+        effective.set_if_neq(EffectiveStats {
+            attack: stats.attack + bonus.attack,
+            defense: stats.defense + bonus.defense,
+            speed: stats.speed + bonus.speed,
+        });
+    }
+}
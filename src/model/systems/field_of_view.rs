@@ -1,11 +1,15 @@
 use bevy::prelude::*;
 use bracket_lib::prelude::{field_of_view, Point};
 
-use crate::model::components::{FieldOfView, Position};
+use crate::model::components::{FieldOfView, Player, Position};
 use crate::model::resources::Map;
+use crate::model::systems::MoveDirection;
 
-pub fn update_fov(mut map: ResMut<Map>, mut query: Query<(&Position, &mut FieldOfView)>) {
-    for (position, mut fov) in query.iter_mut() {
+pub fn update_fov(
+    mut map: ResMut<Map>,
+    mut query: Query<(&Position, &mut FieldOfView, Has<Player>)>,
+) {
+    for (position, mut fov, is_player) in query.iter_mut() {
         if fov.is_dirty {
             fov.visible_tiles.clear();
 
@@ -19,8 +23,10 @@ pub fn update_fov(mut map: ResMut<Map>, mut query: Query<(&Position, &mut FieldO
                 fov.visible_tiles.insert(pos);
             }
 
-            // Update map visibility
-            map.set_visibility(&fov.visible_tiles);
+            // Only the player's sight reveals the map
+            if is_player {
+                map.set_visibility(&fov.visible_tiles);
+            }
 
             fov.is_dirty = false;
         }
@@ -42,19 +48,18 @@ impl bracket_lib::prelude::BaseMap for Map {
     ) -> bracket_lib::prelude::SmallVec<[(usize, f32); 10]> {
         let mut exits = bracket_lib::prelude::SmallVec::new();
         let (x, y) = self.idx_xy(idx);
-
-        // Check cardinal directions
-        let directions = [
-            (x, y - 1), // North
-            (x + 1, y), // East
-            (x, y + 1), // South
-            (x - 1, y), // West
-        ];
-
-        for (nx, ny) in directions.iter() {
-            if self.in_bounds(*nx, *ny) && !self.is_blocked(*nx, *ny) {
-                let new_idx = self.xy_idx(*nx, *ny);
-                exits.push((new_idx, 1.0));
+        let from = Position::new(x, y);
+
+        // Diagonal steps cost more so paths don't zig-zag
+        for direction in MoveDirection::ALL {
+            if self.can_step(from, direction.delta()) {
+                let to = from + direction.delta();
+                let cost = if direction.is_diagonal() {
+                    std::f32::consts::SQRT_2
+                } else {
+                    1.0
+                };
+                exits.push((self.xy_idx(to.x, to.y), cost));
             }
         }
 
@@ -67,8 +72,8 @@ impl bracket_lib::prelude::BaseMap for Map {
         let dx = (x1 - x2).abs() as f32;
         let dy = (y1 - y2).abs() as f32;
 
-        // Euclidean distance
-        (dx * dx + dy * dy).sqrt()
+        // Octile distance, matching the step costs above
+        dx.max(dy) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dy)
     }
 }
 
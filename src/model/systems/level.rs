@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+
+use crate::model::components::{
+    FieldOfView, MapLevel, Player, Position, StashedPosition, TerrainType,
+};
+use crate::model::events::ChangeLevel;
+use crate::model::map_builders::build_level;
+use crate::model::resources::{
+    Dungeon, GameLog, GameRng, LevelLayout, LogCategory, Map, PrefabLibrary,
+};
+use crate::model::systems::{place_prefab_contents, populate_items, populate_monsters};
+use crate::AppSettings;
+
+/// Moves the player between dungeon levels.
+///
+/// The level being left is stashed in `Dungeon` and its entities lose their `Position`,
+/// which keeps them out of every system until the player comes back. Levels that were
+/// never visited are generated and populated on arrival.
+pub fn change_level(
+    mut commands: Commands,
+    mut level_events: EventReader<ChangeLevel>,
+    mut dungeon: ResMut<Dungeon>,
+    mut map: ResMut<Map>,
+    mut layout: ResMut<LevelLayout>,
+    mut game_log: ResMut<GameLog>,
+    mut rng: ResMut<GameRng>,
+    prefabs: Res<PrefabLibrary>,
+    settings: Res<AppSettings>,
+    mut player_query: Query<(&mut Position, &mut FieldOfView), With<Player>>,
+    level_query: Query<
+        (
+            Entity,
+            &MapLevel,
+            Option<&Position>,
+            Option<&StashedPosition>,
+        ),
+        Without<Player>,
+    >,
+) {
+    let Some(change) = level_events.read().last().copied() else {
+        return;
+    };
+    let Ok((mut player_position, mut fov)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let previous_depth = dungeon.depth;
+    if change.depth < 1 || change.depth == previous_depth {
+        return;
+    }
+
+    // Put the level we are leaving to sleep
+    for (entity, level, position, _) in level_query.iter() {
+        if level.0 != previous_depth {
+            continue;
+        }
+        if let Some(position) = position {
+            commands
+                .entity(entity)
+                .remove::<Position>()
+                .insert(StashedPosition(*position));
+        }
+    }
+    let mut previous_map = std::mem::replace(
+        &mut *map,
+        Map::new(settings.map_width(), settings.map_height()),
+    );
+    previous_map.clear_visibility();
+    dungeon.stashed_levels.insert(previous_depth, previous_map);
+    dungeon.depth = change.depth;
+
+    // Arrive on the stairs leading back the way we came
+    let descending = change.depth > previous_depth;
+    let arrival = if descending {
+        TerrainType::UpStairs
+    } else {
+        TerrainType::DownStairs
+    };
+
+    if let Some(stashed) = dungeon.stashed_levels.remove(&change.depth) {
+        *map = stashed;
+        for (entity, level, _, stashed_position) in level_query.iter() {
+            if level.0 != change.depth {
+                continue;
+            }
+            if let Some(stashed_position) = stashed_position {
+                commands
+                    .entity(entity)
+                    .remove::<StashedPosition>()
+                    .insert(stashed_position.0);
+            }
+        }
+        *player_position = map.find_terrain(&arrival).unwrap_or(*player_position);
+    } else {
+        let rng = &mut *rng;
+        rng.enter_level(change.depth);
+        (*map, *layout) = build_level(
+            settings.map_builder,
+            settings.map_width(),
+            settings.map_height(),
+            change.depth,
+            &prefabs,
+            &mut rng.map,
+        );
+
+        *player_position = map.find_terrain(&arrival).unwrap_or(layout.start);
+
+        populate_monsters(
+            &mut commands,
+            &map,
+            &layout,
+            *player_position,
+            change.depth,
+            &mut rng.spawn,
+        );
+        populate_items(&mut commands, &map, &layout, change.depth, &mut rng.spawn);
+        place_prefab_contents(&mut commands, &layout, change.depth);
+    }
+    fov.is_dirty = true;
+
+    let message = if descending {
+        format!("You descend to depth {}.", change.depth)
+    } else {
+        format!("You climb back up to depth {}.", change.depth)
+    };
+    game_log.add_entry_srgb(LogCategory::Movement, message, Color::srgb(1.0, 1.0, 1.0));
+}
@@ -1,9 +1,23 @@
+mod ai;
+mod combat;
+mod effects;
+mod equipment;
 mod field_of_view;
+mod level;
 mod map_generation;
 mod movement;
+mod persistence;
 mod spawner;
+mod turn;
 
+pub use ai::*;
+pub use combat::*;
+pub use effects::*;
+pub use equipment::*;
 pub use field_of_view::*;
+pub use level::*;
 pub use map_generation::*;
 pub use movement::*;
+pub use persistence::*;
 pub use spawner::*;
+pub use turn::*;
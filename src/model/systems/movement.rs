@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 
-use crate::model::components::{FieldOfView, Position};
+use crate::model::components::{FieldOfView, Position, Stats};
 use crate::model::resources::Map;
+use crate::AppSettings;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MoveDirection {
@@ -9,17 +10,70 @@ pub enum MoveDirection {
     South,
     East,
     West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
 }
 
 impl MoveDirection {
+    pub const ALL: [MoveDirection; 8] = [
+        MoveDirection::North,
+        MoveDirection::South,
+        MoveDirection::East,
+        MoveDirection::West,
+        MoveDirection::NorthEast,
+        MoveDirection::NorthWest,
+        MoveDirection::SouthEast,
+        MoveDirection::SouthWest,
+    ];
+
+    /// The four straight directions, for anything that shouldn't move diagonally
+    pub const CARDINALS: [MoveDirection; 4] = [
+        MoveDirection::North,
+        MoveDirection::South,
+        MoveDirection::East,
+        MoveDirection::West,
+    ];
+
     pub fn delta(&self) -> (i32, i32) {
         match self {
             MoveDirection::North => (0, -1),
             MoveDirection::South => (0, 1),
             MoveDirection::East => (1, 0),
             MoveDirection::West => (-1, 0),
+            MoveDirection::NorthEast => (1, -1),
+            MoveDirection::NorthWest => (-1, -1),
+            MoveDirection::SouthEast => (1, 1),
+            MoveDirection::SouthWest => (-1, 1),
         }
     }
+
+    /// The direction of a single step, `None` unless the tiles are neighbours
+    pub fn from_delta(delta: (i32, i32)) -> Option<MoveDirection> {
+        MoveDirection::ALL
+            .into_iter()
+            .find(|direction| direction.delta() == delta)
+    }
+
+    pub const fn is_diagonal(&self) -> bool {
+        matches!(
+            self,
+            MoveDirection::NorthEast
+                | MoveDirection::NorthWest
+                | MoveDirection::SouthEast
+                | MoveDirection::SouthWest
+        )
+    }
+}
+
+/// What happened when an entity tried to step in a direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveOutcome {
+    Moved,
+    Blocked,
+    /// Another actor stands in the way and gets attacked instead
+    Attack(Entity),
 }
 
 pub fn try_move_entity(
@@ -28,24 +82,47 @@ pub fn try_move_entity(
     map: &Map,
     position_query: &mut Query<&mut Position>,
     fov_query: &mut Query<&mut FieldOfView>,
-) -> bool {
-    println!("try_move_entity fn");
+    actor_query: &Query<Entity, With<Stats>>,
+) -> MoveOutcome {
+    let Ok(pos) = position_query.get(entity) else {
+        return MoveOutcome::Blocked;
+    };
+    // Squeezing between two walls is neither a move nor an attack
+    if map.is_squeeze(*pos, direction.delta()) {
+        return MoveOutcome::Blocked;
+    }
+
+    let new_position = *pos + direction.delta();
+
+    // Bumping into another actor is an attack
+    if let Some(target) = actor_query.iter().find(|&other| {
+        other != entity
+            && position_query
+                .get(other)
+                .is_ok_and(|other_pos| *other_pos == new_position)
+    }) {
+        return MoveOutcome::Attack(target);
+    }
+
+    if map.is_blocked(new_position.x, new_position.y) {
+        return MoveOutcome::Blocked;
+    }
+
     if let Ok(mut pos) = position_query.get_mut(entity) {
-        let (dx, dy) = direction.delta();
-        let new_x = pos.x + dx;
-        let new_y = pos.y + dy;
+        *pos = new_position;
+    }
 
-        if !map.is_blocked(new_x, new_y) {
-            pos.x = new_x;
-            pos.y = new_y;
+    // Mark FOV as dirty if entity has one
+    if let Ok(mut fov) = fov_query.get_mut(entity) {
+        fov.is_dirty = true;
+    }
 
-            // Mark FOV as dirty if entity has one
-            if let Ok(mut fov) = fov_query.get_mut(entity) {
-                fov.is_dirty = true;
-            }
+    MoveOutcome::Moved
+}
 
-            return true;
-        }
+/// Copies the diagonal movement rule from the settings onto the map, which pathing reads it from.
+pub fn sync_movement_rules(settings: Res<AppSettings>, mut map: ResMut<Map>) {
+    if map.forbid_diagonal_squeeze != settings.forbid_diagonal_squeeze {
+        map.forbid_diagonal_squeeze = settings.forbid_diagonal_squeeze;
     }
-    false
 }
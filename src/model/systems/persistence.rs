@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::model::commands::{delete_save, SaveGame};
+use crate::RunningState;
+
+/// Saves the run when the game is closed in the middle of it.
+pub fn save_on_exit(
+    mut commands: Commands,
+    mut exit_events: EventReader<AppExit>,
+    state: Res<State<RunningState>>,
+) {
+    if exit_events.read().last().is_none() {
+        return;
+    }
+
+    if matches!(state.get(), RunningState::Running | RunningState::Paused) {
+        commands.queue(SaveGame);
+    }
+}
+
+/// Death is permanent, the save of a finished run can't be continued.
+pub fn discard_save() {
+    delete_save();
+}
@@ -1,22 +1,17 @@
 use bevy::prelude::*;
 use bracket_lib::prelude::RandomNumberGenerator;
+use serde::Deserialize;
 
 use crate::model::components::*;
-use crate::model::resources::Map;
-use crate::RunningState;
-
-pub fn spawn_player(mut commands: Commands, map: Res<Map>) -> Entity {
-    // Find a valid position for the player (first floor tile)
-    let mut valid_position = Position::new(1, 1);
-    for y in 1..map.height - 1 {
-        for x in 1..map.width - 1 {
-            let idx = map.xy_idx(x, y);
-            if map.tiles[idx].is_walkable() {
-                valid_position = Position::new(x, y);
-                break;
-            }
-        }
-    }
+use crate::model::map_builders::PrefabSpawn;
+use crate::model::resources::{
+    Dungeon, GameLog, GameRng, LevelLayout, Map, StatusClock, TurnState,
+};
+use crate::AppSettings;
+
+/// Spawns the player on the start the map generator picked
+pub fn spawn_player(mut commands: Commands, layout: Res<LevelLayout>) -> Entity {
+    let valid_position = layout.start;
 
     println!("Spawned player at {:?}", valid_position);
 
@@ -41,27 +36,54 @@ pub fn spawn_player(mut commands: Commands, map: Res<Map>) -> Entity {
 pub fn spawn_monsters(
     mut commands: Commands,
     map: Res<Map>,
+    layout: Res<LevelLayout>,
+    dungeon: Res<Dungeon>,
+    mut rng: ResMut<GameRng>,
     player_query: Query<&Position, With<Player>>,
 ) {
-    let mut rng = RandomNumberGenerator::new();
-    let num_monsters = rng.range(5, 15);
     let Ok(player_pos) = player_query.get_single() else {
         return;
     };
 
+    populate_monsters(
+        &mut commands,
+        &map,
+        &layout,
+        *player_pos,
+        dungeon.depth,
+        &mut rng.spawn,
+    );
+}
+
+/// Spawns the monsters for a level at `depth` across its spawn regions, keeping them away
+/// from the player.
+///
+/// Deeper levels hold more monsters, a larger share of orcs and tougher individuals.
+pub fn populate_monsters(
+    commands: &mut Commands,
+    map: &Map,
+    layout: &LevelLayout,
+    player_pos: Position,
+    depth: i32,
+    rng: &mut RandomNumberGenerator,
+) {
+    let num_monsters =
+        (rng.range(4 + depth, 13 + depth * 2) as f32 * map.size_factor()).round() as i32;
+    let orc_chance = (20 + (depth - 1) * 10).min(70);
+
     for _ in 0..num_monsters {
         let mut valid_position = None;
         let mut attempts = 0;
 
         // Find a valid position for the monster
         while valid_position.is_none() && attempts < 100 {
-            let x = rng.range(1, map.width - 1);
-            let y = rng.range(1, map.height - 1);
-            let idx = map.xy_idx(x, y);
+            attempts += 1;
+            let Some(pos) = layout.random_spawn_tile(rng) else {
+                continue;
+            };
 
             // Check if the position is valid (not blocked and not too close to player)
-            if map.tiles[idx].is_walkable() {
-                let pos = Position::new(x, y);
+            if !map.is_blocked(pos.x, pos.y) {
                 let distance_to_player =
                     ((pos.x - player_pos.x).pow(2) + (pos.y - player_pos.y).pow(2)) as f32;
 
@@ -69,50 +91,97 @@ pub fn spawn_monsters(
                     valid_position = Some(pos);
                 }
             }
-
-            attempts += 1;
         }
 
         if let Some(pos) = valid_position {
-            // Randomly choose between two monster types
-            if rng.range(0, 100) < 80 {
-                // Goblin (more common)
-                commands.spawn((
-                    Monster,
-                    pos,
-                    Renderable::new(
-                        'g',
-                        Color::srgb(0.0, 0.7, 0.0),
-                        Color::srgb(0.0, 0.0, 0.0),
-                        1,
-                    ),
-                    FieldOfView::new(6),
-                    Stats::new(8, 3, 1, 3),
-                    Name("Goblin".to_string()),
-                ));
+            // Goblins are more common, orcs take over deeper down
+            let kind = if rng.range(0, 100) >= orc_chance {
+                MonsterKind::Goblin
             } else {
-                // Orc (less common, stronger)
-                commands.spawn((
-                    Monster,
-                    pos,
-                    Renderable::new(
-                        'o',
-                        Color::srgb(0.7, 0.0, 0.0),
-                        Color::srgb(0.0, 0.0, 0.0),
-                        1,
-                    ),
-                    FieldOfView::new(7),
-                    Stats::new(15, 4, 2, 2),
-                    Name("Orc".to_string()),
-                ));
-            }
+                MonsterKind::Orc
+            };
+            spawn_monster(commands, kind, pos, depth);
+        }
+    }
+}
+
+/// The monsters the dungeon knows how to spawn, also named by prefab legends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonsterKind {
+    Goblin,
+    Orc,
+}
+
+/// Spawns a monster toughened for `depth`
+pub fn spawn_monster(commands: &mut Commands, kind: MonsterKind, pos: Position, depth: i32) {
+    let bonus_health = (depth - 1) * 2;
+
+    match kind {
+        MonsterKind::Goblin => {
+            commands.spawn((
+                Monster,
+                pos,
+                MapLevel(depth),
+                Renderable::new(
+                    'g',
+                    Color::srgb(0.0, 0.7, 0.0),
+                    Color::srgb(0.0, 0.0, 0.0),
+                    1,
+                ),
+                FieldOfView::new(6),
+                Stats::new(8 + bonus_health, 3, 1, 3),
+                // Goblins are cowardly and restless
+                Ai::new([
+                    Behavior::Flee {
+                        health_fraction: 0.5,
+                    },
+                    Behavior::Chase,
+                    Behavior::Wander { chance: 50 },
+                ]),
+                Name("Goblin".to_string()),
+            ));
+        }
+        MonsterKind::Orc => {
+            commands.spawn((
+                Monster,
+                pos,
+                MapLevel(depth),
+                Renderable::new(
+                    'o',
+                    Color::srgb(0.7, 0.0, 0.0),
+                    Color::srgb(0.0, 0.0, 0.0),
+                    1,
+                ),
+                FieldOfView::new(7),
+                Stats::new(15 + bonus_health, 4 + depth / 3, 2, 2),
+                // Orcs fight to the death and mostly stand guard
+                Ai::new([Behavior::Chase, Behavior::Wander { chance: 20 }]),
+                Name("Orc".to_string()),
+            ));
         }
     }
 }
 
-pub fn spawn_items(mut commands: Commands, map: Res<Map>) {
-    let mut rng = RandomNumberGenerator::new();
-    let num_items = rng.range(3, 8);
+pub fn spawn_items(
+    mut commands: Commands,
+    map: Res<Map>,
+    layout: Res<LevelLayout>,
+    dungeon: Res<Dungeon>,
+    mut rng: ResMut<GameRng>,
+) {
+    populate_items(&mut commands, &map, &layout, dungeon.depth, &mut rng.spawn);
+}
+
+/// Scatters the items for a level at `depth` across its spawn regions.
+pub fn populate_items(
+    commands: &mut Commands,
+    map: &Map,
+    layout: &LevelLayout,
+    depth: i32,
+    rng: &mut RandomNumberGenerator,
+) {
+    let num_items = (rng.range(3, 8) as f32 * map.size_factor()).round() as i32;
 
     for _ in 0..num_items {
         let mut valid_position = None;
@@ -120,83 +189,211 @@ pub fn spawn_items(mut commands: Commands, map: Res<Map>) {
 
         // Find a valid position for the item
         while valid_position.is_none() && attempts < 100 {
-            let x = rng.range(1, map.width - 1);
-            let y = rng.range(1, map.height - 1);
-            let idx = map.xy_idx(x, y);
-
-            if map.tiles[idx].is_walkable() {
-                valid_position = Some(Position::new(x, y));
-            }
-
             attempts += 1;
+            valid_position = layout
+                .random_spawn_tile(rng)
+                .filter(|pos| !map.is_blocked(pos.x, pos.y));
         }
 
         if let Some(pos) = valid_position {
-            // Randomly choose item type
-            let item_roll = rng.range(0, 100);
-
-            if item_roll < 70 {
-                // Health potion
-                commands.spawn((
-                    Item,
-                    Consumable,
-                    pos,
-                    Renderable::new(
-                        '!',
-                        Color::srgb(0.8, 0.0, 0.8),
-                        Color::srgb(0.0, 0.0, 0.0),
-                        2,
-                    ),
-                    Name("Health Potion".to_string()),
-                ));
-            } else if item_roll < 90 {
-                // Sword
-                commands.spawn((
-                    Item,
-                    Equippable {
-                        slot: EquipmentSlot::MainHand,
-                    },
-                    pos,
-                    Renderable::new(
-                        '/',
-                        Color::srgb(0.8, 0.8, 0.8),
-                        Color::srgb(0.0, 0.0, 0.0),
-                        2,
-                    ),
-                    Name("Sword".to_string()),
-                ));
-            } else {
-                // Shield
-                commands.spawn((
-                    Item,
-                    Equippable {
-                        slot: EquipmentSlot::OffHand,
-                    },
-                    pos,
-                    Renderable::new(
-                        '[',
-                        Color::srgb(0.8, 0.6, 0.2),
-                        Color::srgb(0.0, 0.0, 0.0),
-                        2,
-                    ),
-                    Name("Shield".to_string()),
-                ));
-            }
+            let kind = match rng.range(0, 100) {
+                0..=39 => ItemKind::HealthPotion,
+                40..=49 => ItemKind::Ration,
+                50..=59 => ItemKind::FireballScroll,
+                60..=69 => ItemKind::ConfusionScroll,
+                70..=89 => ItemKind::Sword,
+                _ => ItemKind::Shield,
+            };
+            spawn_item(commands, kind, pos, depth);
+        }
+    }
+}
+
+/// The items the dungeon knows how to spawn, also named by prefab legends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    HealthPotion,
+    Ration,
+    FireballScroll,
+    ConfusionScroll,
+    Sword,
+    Shield,
+}
+
+pub fn spawn_prefab_contents(
+    mut commands: Commands,
+    layout: Res<LevelLayout>,
+    dungeon: Res<Dungeon>,
+) {
+    place_prefab_contents(&mut commands, &layout, dungeon.depth);
+}
+
+/// Spawns the monsters and items prefabs put on the level
+pub fn place_prefab_contents(commands: &mut Commands, layout: &LevelLayout, depth: i32) {
+    for (pos, spawn) in &layout.spawns {
+        match spawn {
+            PrefabSpawn::Monster(kind) => spawn_monster(commands, *kind, *pos, depth),
+            PrefabSpawn::Item(kind) => spawn_item(commands, *kind, *pos, depth),
+        }
+    }
+}
+
+pub fn spawn_item(commands: &mut Commands, kind: ItemKind, pos: Position, depth: i32) {
+    match kind {
+        ItemKind::HealthPotion => {
+            commands.spawn((
+                Item,
+                Consumable,
+                ProvidesHealing { amount: 8 },
+                pos,
+                MapLevel(depth),
+                Renderable::new(
+                    '!',
+                    Color::srgb(0.8, 0.0, 0.8),
+                    Color::srgb(0.0, 0.0, 0.0),
+                    2,
+                ),
+                Name("Health Potion".to_string()),
+                Description::new("A small flask of red liquid that closes wounds."),
+            ));
+        }
+        ItemKind::Ration => {
+            commands.spawn((
+                Item,
+                Consumable,
+                ProvidesHealing { amount: 3 },
+                pos,
+                MapLevel(depth),
+                Renderable::new(
+                    '%',
+                    Color::srgb(0.8, 0.6, 0.3),
+                    Color::srgb(0.0, 0.0, 0.0),
+                    2,
+                ),
+                Name("Ration".to_string()),
+                Description::new("Dry bread and salted meat. Not tasty, but filling."),
+            ));
+        }
+        ItemKind::FireballScroll => {
+            commands.spawn((
+                Item,
+                Consumable,
+                Ranged { range: 6 },
+                InflictsDamage { amount: 20 },
+                AreaOfEffect { radius: 3 },
+                pos,
+                MapLevel(depth),
+                Renderable::new(
+                    '?',
+                    Color::srgb(1.0, 0.5, 0.0),
+                    Color::srgb(0.0, 0.0, 0.0),
+                    2,
+                ),
+                Name("Fireball Scroll".to_string()),
+                Description::new(
+                    "Unleashes a ball of fire that scorches everything near where it lands.",
+                ),
+            ));
+        }
+        ItemKind::ConfusionScroll => {
+            commands.spawn((
+                Item,
+                Consumable,
+                Ranged { range: 6 },
+                InflictsStatus {
+                    kind: StatusKind::Confused,
+                    duration: 4,
+                },
+                pos,
+                MapLevel(depth),
+                Renderable::new(
+                    '?',
+                    Color::srgb(1.0, 0.4, 1.0),
+                    Color::srgb(0.0, 0.0, 0.0),
+                    2,
+                ),
+                Name("Confusion Scroll".to_string()),
+                Description::new("Muddles the mind of whoever stands where it is read at."),
+            ));
+        }
+        ItemKind::Sword => {
+            commands.spawn((
+                Item,
+                Equippable {
+                    slot: EquipmentSlot::MainHand,
+                },
+                StatBonus {
+                    attack: 2,
+                    ..default()
+                },
+                pos,
+                MapLevel(depth),
+                Renderable::new(
+                    '/',
+                    Color::srgb(0.8, 0.8, 0.8),
+                    Color::srgb(0.0, 0.0, 0.0),
+                    2,
+                ),
+                Name("Sword".to_string()),
+                Description::new("A plain but well balanced blade."),
+            ));
+        }
+        ItemKind::Shield => {
+            commands.spawn((
+                Item,
+                Equippable {
+                    slot: EquipmentSlot::OffHand,
+                },
+                StatBonus {
+                    defense: 2,
+                    ..default()
+                },
+                pos,
+                MapLevel(depth),
+                Renderable::new(
+                    '[',
+                    Color::srgb(0.8, 0.6, 0.2),
+                    Color::srgb(0.0, 0.0, 0.0),
+                    2,
+                ),
+                Name("Shield".to_string()),
+                Description::new("A battered wooden shield, still sturdy enough to turn a blow."),
+            ));
         }
     }
 }
 
-pub fn setup_game(mut map: ResMut<Map>, mut next_state: ResMut<NextState<RunningState>>) {
+/// Wipes the previous run so `generate_map` can build a fresh dungeon.
+pub fn setup_game(
+    mut commands: Commands,
+    mut map: ResMut<Map>,
+    mut dungeon: ResMut<Dungeon>,
+    mut rng: ResMut<GameRng>,
+    mut turn_state: ResMut<TurnState>,
+    mut status_clock: ResMut<StatusClock>,
+    mut game_log: ResMut<GameLog>,
+    settings: Res<AppSettings>,
+    entity_query: Query<Entity, Or<(With<Position>, With<Item>, With<MapLevel>)>>,
+) {
     println!("Setting up game");
 
-    // Reset the map
-    *map = Map::default();
+    // Remove everything left over from a previous run
+    for entity in entity_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
 
-    // Set the game to the Running state
-    next_state.set(RunningState::Running);
+    // Start from solid rock, the generator carves the dungeon out of it
+    *map = Map::new(settings.map_width(), settings.map_height());
+    *dungeon = Dungeon::default();
+    rng.reset();
+    info!("Dungeon seed {}", rng.seed());
+    *turn_state = TurnState::default();
+    *status_clock = StatusClock::default();
+    *game_log = GameLog::default();
 }
 
-pub fn spawn_initial_player(commands: Commands, map: Res<Map>) {
+pub fn spawn_initial_player(commands: Commands, layout: Res<LevelLayout>) {
     println!("Spawning initial player");
-    spawn_player(commands, map);
+    spawn_player(commands, layout);
 }
@@ -0,0 +1,118 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::model::components::{Ai, EffectiveStats, Energy, Player, Position};
+use crate::model::resources::{GameLog, TurnState};
+use crate::model::ModelConstants;
+use crate::TurnPhase;
+
+/// Upper bound on world ticks simulated in a single frame
+const MAX_TICKS_PER_FRAME: u32 = 100;
+
+/// Advances world time until someone can act.
+///
+/// Every tick each actor gains energy equal to its speed. When the player can afford an
+/// action they become the current entity in `TurnState` and the game waits for input.
+/// Actors left behind on other levels have no `Position` and sit out until the player returns.
+pub fn schedule_turns(
+    mut turn_state: ResMut<TurnState>,
+    mut next_phase: ResMut<NextState<TurnPhase>>,
+    mut actor_query: Query<
+        (Entity, &EffectiveStats, &mut Energy, Has<Player>, Has<Ai>),
+        With<Position>,
+    >,
+) {
+    for _ in 0..MAX_TICKS_PER_FRAME {
+        // The player goes first on ties
+        if let Some((player_entity, _, mut energy, _, _)) =
+            actor_query.iter_mut().find(|(_, _, energy, is_player, _)| {
+                *is_player && energy.0 >= ModelConstants::ACTION_COST
+            })
+        {
+            turn_state.set_current_entity(player_entity, energy.0);
+            energy.0 = 0;
+            next_phase.set(TurnPhase::AwaitingInput);
+            return;
+        }
+
+        // Let the monsters spend what they have before time moves on
+        if actor_query
+            .iter()
+            .any(|(_, _, energy, _, has_ai)| has_ai && energy.0 >= ModelConstants::ACTION_COST)
+        {
+            return;
+        }
+
+        if actor_query.is_empty() {
+            return;
+        }
+
+        for (_, stats, mut energy, _, _) in actor_query.iter_mut() {
+            energy.0 += stats.speed.max(1);
+        }
+        turn_state.next_turn();
+    }
+}
+
+/// Hands the turn in progress back to the player as energy.
+///
+/// Leaving `Running` tears down `TurnPhase`, which comes back as `MonsterTurn`, so without
+/// this the points already taken out of `Energy` would be lost.
+pub fn refund_player_turn(
+    mut turn_state: ResMut<TurnState>,
+    mut player_query: Query<&mut Energy, With<Player>>,
+) {
+    let action_points = turn_state.action_points;
+    turn_state.clear_current_entity();
+    if let Ok(mut energy) = player_query.get_single_mut() {
+        energy.0 = energy.0.max(action_points);
+    }
+}
+
+/// Stamps new log entries with the current world turn.
+pub fn sync_log_turn(turn_state: Res<TurnState>, mut game_log: ResMut<GameLog>) {
+    if game_log.turn != turn_state.turn_number {
+        game_log.turn = turn_state.turn_number;
+    }
+}
+
+/// Turn bookkeeping for observers that act on behalf of the player.
+#[derive(SystemParam)]
+pub struct PlayerTurn<'w, 's> {
+    turn_phase: Option<Res<'w, State<TurnPhase>>>,
+    next_phase: ResMut<'w, NextState<TurnPhase>>,
+    turn_state: ResMut<'w, TurnState>,
+    energy_query: Query<'w, 's, &'static mut Energy>,
+}
+
+impl PlayerTurn<'_, '_> {
+    /// Whether `entity` holds the current turn and may act
+    pub fn is_turn_of(&self, entity: Entity) -> bool {
+        self.turn_phase
+            .as_ref()
+            .is_some_and(|phase| *phase.get() == TurnPhase::AwaitingInput)
+            && self.turn_state.current_entity == Some(entity)
+    }
+
+    /// Where the run stands, for reseeding the rolls of the player's action
+    pub fn turn_state(&self) -> &TurnState {
+        &self.turn_state
+    }
+
+    /// Spends `cost` action points and lets the monsters move once the player runs dry.
+    pub fn spend(&mut self, cost: i32) {
+        self.turn_state.consume_action_points(cost);
+        if self.turn_state.action_points >= ModelConstants::ACTION_COST {
+            return;
+        }
+
+        // Leftover points carry over into the next turn
+        if let Some(entity) = self.turn_state.current_entity {
+            if let Ok(mut energy) = self.energy_query.get_mut(entity) {
+                energy.0 = self.turn_state.action_points;
+            }
+        }
+
+        self.turn_state.clear_current_entity();
+        self.next_phase.set(TurnPhase::MonsterTurn);
+    }
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+/// Camera that draws the UI on top of the map
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UiCamera;
+
+/// Root of the stats panel and message log shown while playing
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hud;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlayerNameText;
+
+/// Filled part of the health bar, its width is the health fraction
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HealthBarFill;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StatsText;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LogPanel;
+
+/// Text whose spans are the visible `GameLog` entries
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LogText;
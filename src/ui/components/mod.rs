@@ -1 +1,3 @@
+mod hud;
 
+pub use hud::*;
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use bracket_lib::bevy::*;
+use bracket_lib::prelude::RGB;
+
+use crate::controller::resources::{InputAction, Keymap};
+use crate::model::resources::{GameLog, GameRng, TurnState};
+use crate::view::ViewConstants;
+
+/// How many of the final log messages are shown on the summary
+const LAST_WORDS: usize = 5;
+
+pub fn render_game_over(
+    ctx: Res<BracketContext>,
+    turn_state: Res<TurnState>,
+    game_log: Res<GameLog>,
+    rng: Res<GameRng>,
+    keymap: Res<Keymap>,
+) {
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+    draw_batch.cls();
+
+    let black = RGB::from_f32(0.0, 0.0, 0.0);
+    let white = ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), black);
+
+    draw_batch.print_color_centered(
+        15,
+        "You are dead.",
+        ColorPair::new(RGB::from_f32(1.0, 0.0, 0.0), black),
+    );
+    draw_batch.print_color_centered(
+        17,
+        format!("You survived {} turns.", turn_state.turn_number),
+        white,
+    );
+    draw_batch.print_color_centered(
+        18,
+        format!("Seed {}", rng.seed()),
+        ColorPair::new(RGB::from_f32(0.5, 0.5, 0.5), black),
+    );
+
+    // Replay how it ended
+    let skip = game_log.entries.len().saturating_sub(LAST_WORDS);
+    for (i, entry) in game_log.entries.iter().skip(skip).enumerate() {
+        draw_batch.print_color_centered(
+            20 + i as i32,
+            entry.display_text(),
+            ColorPair::new(entry.color, black),
+        );
+    }
+
+    draw_batch.print_color_centered(
+        27,
+        format!(
+            "Press {} to descend again.",
+            keymap.hint(InputAction::Confirm)
+        ),
+        ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), black),
+    );
+
+    ctx.submit_batch(0, draw_batch);
+}
@@ -0,0 +1,280 @@
+use bevy::{
+    prelude::*,
+    render::camera::{ClearColorConfig, Viewport},
+    window::PrimaryWindow,
+};
+
+use crate::model::components::{EffectiveStats, Name, Player, Stats};
+use crate::model::resources::{Dungeon, GameLog, GameRng, TurnState};
+use crate::ui::components::*;
+use crate::ui::UiConstants;
+use crate::RunningState;
+
+const HEALTH_GOOD: Color = Color::srgb(0.0, 0.8, 0.0);
+const HEALTH_HURT: Color = Color::srgb(0.9, 0.8, 0.0);
+const HEALTH_CRITICAL: Color = Color::srgb(0.9, 0.0, 0.0);
+const HEALTH_BAR_BACKGROUND: Color = Color::srgb(0.15, 0.15, 0.15);
+
+pub fn spawn_ui_camera(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Camera {
+            // Draw over the map without clearing it
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+        UiConstants::UI_LAYER,
+        UiCamera,
+    ));
+}
+
+/// Lays out the stats panel on the right and the message log along the bottom.
+///
+/// Sizes come from `UiConstants` and flex layout, so the panels follow the window.
+pub fn spawn_hud(mut commands: Commands, camera_query: Query<Entity, With<UiCamera>>) {
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+
+    let text_font = TextFont {
+        font_size: UiConstants::FONT_SIZE,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Hud,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            Visibility::Hidden,
+            TargetCamera(camera),
+        ))
+        .with_children(|root| {
+            root.spawn(Node {
+                flex_grow: 1.0,
+                flex_direction: FlexDirection::Row,
+                ..default()
+            })
+            .with_children(|top| {
+                // The map shows through here
+                top.spawn(Node {
+                    flex_grow: 1.0,
+                    ..default()
+                });
+
+                top.spawn((
+                    Node {
+                        width: Val::Px(UiConstants::STATS_WIDTH as f32),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(UiConstants::PANEL_PADDING)),
+                        row_gap: Val::Px(UiConstants::PANEL_PADDING),
+                        ..default()
+                    },
+                    BackgroundColor(UiConstants::STATS_BACKGROUND.into()),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        PlayerNameText,
+                        Text::default(),
+                        text_font.clone(),
+                        TextColor(Color::WHITE),
+                    ));
+
+                    panel
+                        .spawn((
+                            Node {
+                                width: Val::Percent(100.0),
+                                height: Val::Px(UiConstants::FONT_SIZE),
+                                ..default()
+                            },
+                            BackgroundColor(HEALTH_BAR_BACKGROUND),
+                        ))
+                        .with_children(|bar| {
+                            bar.spawn((
+                                HealthBarFill,
+                                Node {
+                                    width: Val::Percent(100.0),
+                                    height: Val::Percent(100.0),
+                                    ..default()
+                                },
+                                BackgroundColor(HEALTH_GOOD),
+                            ));
+                        });
+
+                    panel.spawn((
+                        StatsText,
+                        Text::default(),
+                        text_font.clone(),
+                        TextColor(Color::WHITE),
+                    ));
+                });
+            });
+
+            root.spawn((
+                LogPanel,
+                Node {
+                    height: Val::Px(UiConstants::LOG_HEIGHT as f32),
+                    padding: UiRect::all(Val::Px(UiConstants::PANEL_PADDING)),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                BackgroundColor(UiConstants::LOG_BACKGROUND.into()),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    LogText,
+                    Text::default(),
+                    text_font,
+                    TextColor(UiConstants::LOG_FOREGROUND.into()),
+                ));
+            });
+        });
+}
+
+/// Shows the HUD only while a run is in progress.
+pub fn sync_hud_visibility(
+    state: Res<State<RunningState>>,
+    mut hud_query: Query<&mut Visibility, With<Hud>>,
+) {
+    let visibility = match state.get() {
+        RunningState::Running | RunningState::Paused => Visibility::Inherited,
+        _ => Visibility::Hidden,
+    };
+
+    for mut hud_visibility in hud_query.iter_mut() {
+        hud_visibility.set_if_neq(visibility);
+    }
+}
+
+/// Keeps the map cameras out of the area covered by the panels while they are shown.
+pub fn fit_map_viewport(
+    state: Res<State<RunningState>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Camera, Without<UiCamera>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    let scale = window.scale_factor();
+    let reserved = match state.get() {
+        RunningState::Running | RunningState::Paused => UVec2::new(
+            (UiConstants::STATS_WIDTH as f32 * scale) as u32,
+            (UiConstants::LOG_HEIGHT as f32 * scale) as u32,
+        ),
+        _ => UVec2::ZERO,
+    };
+    let size = UVec2::new(window.physical_width(), window.physical_height())
+        .saturating_sub(reserved)
+        .max(UVec2::ONE);
+
+    for mut camera in camera_query.iter_mut() {
+        let fits = camera
+            .viewport
+            .as_ref()
+            .is_some_and(|viewport| viewport.physical_size == size);
+        if !fits {
+            camera.viewport = Some(Viewport {
+                physical_position: UVec2::ZERO,
+                physical_size: size,
+                ..default()
+            });
+        }
+    }
+}
+
+pub fn update_stats_panel(
+    dungeon: Res<Dungeon>,
+    turn_state: Res<TurnState>,
+    rng: Res<GameRng>,
+    player_query: Query<(&Name, &Stats, &EffectiveStats), With<Player>>,
+    mut name_query: Query<&mut Text, (With<PlayerNameText>, Without<StatsText>)>,
+    mut stats_query: Query<&mut Text, (With<StatsText>, Without<PlayerNameText>)>,
+    mut bar_query: Query<(&mut Node, &mut BackgroundColor), With<HealthBarFill>>,
+) {
+    let Ok((name, stats, effective)) = player_query.get_single() else {
+        return;
+    };
+
+    let fraction = (stats.health.max(0) as f32 / stats.max_health.max(1) as f32).min(1.0);
+    if let Ok((mut node, mut color)) = bar_query.get_single_mut() {
+        let width = Val::Percent(fraction * 100.0);
+        if node.width != width {
+            node.width = width;
+        }
+        color.set_if_neq(BackgroundColor(health_color(fraction)));
+    }
+
+    if let Ok(mut text) = name_query.get_single_mut() {
+        text.set_if_neq(Text::new(name.0.clone()));
+    }
+
+    if let Ok(mut text) = stats_query.get_single_mut() {
+        text.set_if_neq(Text::new(format!(
+            "HP: {} / {}\nAttack: {}\nDefense: {}\n\nDepth: {}\nTurn: {}\nSeed: {}",
+            stats.health.max(0),
+            stats.max_health,
+            effective.attack,
+            effective.defense,
+            dungeon.depth,
+            turn_state.turn_number,
+            rng.seed(),
+        )));
+    }
+}
+
+fn health_color(fraction: f32) -> Color {
+    if fraction > 0.6 {
+        HEALTH_GOOD
+    } else if fraction > 0.3 {
+        HEALTH_HURT
+    } else {
+        HEALTH_CRITICAL
+    }
+}
+
+/// Fills the log panel with as many of the newest entries as fit, oldest on top.
+pub fn update_log_panel(
+    mut commands: Commands,
+    game_log: Res<GameLog>,
+    mut shown_lines: Local<usize>,
+    panel_query: Query<&ComputedNode, With<LogPanel>>,
+    log_query: Query<Entity, With<LogText>>,
+) {
+    let (Ok(panel), Ok(log_text)) = (panel_query.get_single(), log_query.get_single()) else {
+        return;
+    };
+
+    let height = panel.size().y * panel.inverse_scale_factor() - UiConstants::PANEL_PADDING * 2.0;
+    let lines = (height / UiConstants::LOG_LINE_HEIGHT).floor().max(1.0) as usize;
+    if !game_log.is_changed() && lines == *shown_lines {
+        return;
+    }
+    *shown_lines = lines;
+
+    let skip = game_log.entries.len().saturating_sub(lines);
+    let mut log_text = commands.entity(log_text);
+    log_text.despawn_descendants();
+    log_text.with_children(|text| {
+        for (i, entry) in game_log.entries.iter().skip(skip).enumerate() {
+            let line = if i == 0 {
+                entry.display_text()
+            } else {
+                format!("\n{}", entry.display_text())
+            };
+            text.spawn((
+                TextSpan::new(line),
+                TextFont {
+                    font_size: UiConstants::FONT_SIZE,
+                    ..default()
+                },
+                TextColor(entry.color),
+            ));
+        }
+    });
+}
@@ -0,0 +1,251 @@
+use bevy::{ecs::query::QueryData, prelude::*};
+use bracket_lib::bevy::*;
+use bracket_lib::prelude::{Rect, RGB};
+
+use crate::controller::resources::{InputAction, InventoryMenu, ItemCommand, Keymap, MenuCursor};
+use crate::model::components::{
+    AreaOfEffect, Consumable, Description, EquipmentSlot, Equippable, Equipped, InBackpack,
+    InflictsDamage, InflictsStatus, Name, Player, ProvidesHealing, Ranged, StatBonus,
+};
+use crate::view::resources::MapCamera;
+use crate::view::ViewConstants;
+
+/// Width of the text inside the detail pane
+const DETAIL_WIDTH: usize = 33;
+
+#[derive(QueryData)]
+pub struct ItemDetails {
+    entity: Entity,
+    name: &'static Name,
+    backpack: &'static InBackpack,
+    description: Option<&'static Description>,
+    healing: Option<&'static ProvidesHealing>,
+    damage: Option<&'static InflictsDamage>,
+    status: Option<&'static InflictsStatus>,
+    ranged: Option<&'static Ranged>,
+    area: Option<&'static AreaOfEffect>,
+    bonus: Option<&'static StatBonus>,
+    equippable: Option<&'static Equippable>,
+    consumable: Has<Consumable>,
+}
+
+impl ItemDetailsItem<'_> {
+    /// One line per thing the item does
+    fn effects(&self) -> Vec<String> {
+        let mut effects = Vec::new();
+        if let Some(healing) = self.healing {
+            effects.push(format!("Heals {} HP", healing.amount));
+        }
+        if let Some(damage) = self.damage {
+            effects.push(format!("Deals {} damage", damage.amount));
+        }
+        if let Some(status) = self.status {
+            effects.push(format!(
+                "Leaves targets {} for {} turns",
+                status.kind.adjective(),
+                status.duration
+            ));
+        }
+        if let Some(ranged) = self.ranged {
+            effects.push(format!("Range {}", ranged.range));
+        }
+        if let Some(area) = self.area {
+            effects.push(format!("Radius {}", area.radius));
+        }
+        if let Some(bonus) = self.bonus {
+            for (label, value) in [
+                ("Attack", bonus.attack),
+                ("Defense", bonus.defense),
+                ("Speed", bonus.speed),
+            ] {
+                if value != 0 {
+                    effects.push(format!("{label} {value:+}"));
+                }
+            }
+        }
+        if let Some(equippable) = self.equippable {
+            effects.push(format!("Worn on: {}", equippable.slot.label()));
+        }
+        effects
+    }
+}
+
+pub fn render_inventory(
+    ctx: Res<BracketContext>,
+    inventory_menu: Res<InventoryMenu>,
+    cursor: Res<MenuCursor>,
+    keymap: Res<Keymap>,
+    player_query: Query<Entity, With<Player>>,
+    item_query: Query<ItemDetails>,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+
+    let black = RGB::from_f32(0.0, 0.0, 0.0);
+    let white = ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), black);
+    let yellow = ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), black);
+    let grey = ColorPair::new(RGB::from_f32(0.5, 0.5, 0.5), black);
+    let highlight = ColorPair::new(black, RGB::from_f32(1.0, 1.0, 0.0));
+
+    let mut carried: Vec<ItemDetailsItem> = item_query
+        .iter()
+        .filter(|item| item.backpack.owner == player_entity)
+        .collect();
+    // Sorted like the hotkeys in `inventory_input`
+    carried.sort_by_key(|item| item.entity);
+
+    let count = carried.len().max(1) as i32;
+    let (x, y, width) = (2, 4, 36);
+
+    draw_batch.draw_box(Rect::with_size(x, y - 2, width, count + 3), white);
+    draw_batch.print_color(Point::new(x + 3, y - 2), "Inventory", yellow);
+    draw_batch.print_color(
+        Point::new(x + 3, y + count + 1),
+        format!("{} to close", keymap.hint(InputAction::Cancel)),
+        yellow,
+    );
+
+    if carried.is_empty() {
+        draw_batch.print_color(Point::new(x + 2, y), "Your backpack is empty.", grey);
+    }
+
+    for (i, item) in carried.iter().enumerate() {
+        let row = y + i as i32;
+        let letter = (b'a' + i as u8) as char;
+        let name_color = if inventory_menu.selected == Some(item.entity) {
+            highlight
+        } else {
+            white
+        };
+
+        // The cursor moves on to the actions once an item is selected
+        if inventory_menu.selected.is_none() && i == cursor.index {
+            draw_batch.print_color(Point::new(x + 1, row), ">", yellow);
+        }
+        draw_batch.print_color(Point::new(x + 2, row), format!("({letter})"), yellow);
+        draw_batch.print_color(Point::new(x + 6, row), &item.name.0, name_color);
+    }
+
+    // Detail pane for the selected item
+    if let Some(item) = carried
+        .iter()
+        .find(|item| Some(item.entity) == inventory_menu.selected)
+    {
+        let (x, width) = (x + width + 2, 37);
+        let mut lines: Vec<(String, ColorPair)> = Vec::new();
+
+        if let Some(description) = item.description {
+            for line in wrap(&description.0, DETAIL_WIDTH) {
+                lines.push((line, white));
+            }
+            lines.push((String::new(), white));
+        }
+        for effect in item.effects() {
+            lines.push((effect, grey));
+        }
+        lines.push((String::new(), white));
+
+        // Only offer what makes sense for this item
+        let first_command = lines.len();
+        for command in ItemCommand::available(item.consumable, item.equippable.is_some()) {
+            lines.push((command.label().to_string(), yellow));
+        }
+
+        draw_batch.draw_box(
+            Rect::with_size(x, y - 2, width, lines.len() as i32 + 3),
+            white,
+        );
+        draw_batch.print_color(Point::new(x + 3, y - 2), &item.name.0, yellow);
+        for (i, (line, color)) in lines.iter().enumerate() {
+            draw_batch.print_color(Point::new(x + 2, y + i as i32), line, *color);
+        }
+        draw_batch.print_color(
+            Point::new(x + 1, y + (first_command + cursor.index) as i32),
+            ">",
+            yellow,
+        );
+    }
+
+    ctx.submit_batch(20000, draw_batch);
+}
+
+pub fn render_equipment(
+    ctx: Res<BracketContext>,
+    camera: Res<MapCamera>,
+    cursor: Res<MenuCursor>,
+    keymap: Res<Keymap>,
+    player_query: Query<Entity, With<Player>>,
+    equipped_query: Query<(&Name, &Equipped)>,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+
+    let black = RGB::from_f32(0.0, 0.0, 0.0);
+    let white = ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), black);
+    let yellow = ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), black);
+    let grey = ColorPair::new(RGB::from_f32(0.5, 0.5, 0.5), black);
+
+    let count = EquipmentSlot::ALL.len() as i32;
+    let width = 45;
+    let (x, y) = ((camera.width - width) / 2, (camera.height - count) / 2);
+
+    draw_batch.draw_box(Rect::with_size(x, y - 2, width, count + 3), white);
+    draw_batch.print_color(Point::new(x + 3, y - 2), "Equipment", yellow);
+    draw_batch.print_color(
+        Point::new(x + 3, y + count + 1),
+        format!(
+            "Letter to take off, {} to close",
+            keymap.hint(InputAction::Cancel)
+        ),
+        yellow,
+    );
+
+    for (i, slot) in EquipmentSlot::ALL.iter().enumerate() {
+        let row = y + i as i32;
+        let letter = (b'a' + i as u8) as char;
+        let worn = equipped_query
+            .iter()
+            .find(|(_, equipped)| equipped.owner == player_entity && equipped.slot == *slot);
+
+        if i == cursor.index {
+            draw_batch.print_color(Point::new(x + 1, row), ">", yellow);
+        }
+        draw_batch.print_color(Point::new(x + 2, row), format!("({letter})"), yellow);
+        draw_batch.print_color(Point::new(x + 6, row), slot.label(), grey);
+        match worn {
+            Some((name, _)) => draw_batch.print_color(Point::new(x + 18, row), &name.0, white),
+            None => draw_batch.print_color(Point::new(x + 18, row), "-", grey),
+        };
+    }
+
+    ctx.submit_batch(20000, draw_batch);
+}
+
+/// Breaks `text` into lines of at most `width` characters on word boundaries
+pub(crate) fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bracket_lib::bevy::*;
+use bracket_lib::prelude::{Rect, RGB};
+
+use crate::controller::resources::{InputAction, Keymap, MenuCursor, PickupChoices};
+use crate::model::components::Name;
+use crate::view::resources::MapCamera;
+use crate::view::ViewConstants;
+
+pub fn render_pickup_menu(
+    ctx: Res<BracketContext>,
+    camera: Res<MapCamera>,
+    pickup_choices: Res<PickupChoices>,
+    cursor: Res<MenuCursor>,
+    keymap: Res<Keymap>,
+    name_query: Query<&Name>,
+) {
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+
+    let black = RGB::from_f32(0.0, 0.0, 0.0);
+    let white = ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), black);
+    let yellow = ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), black);
+
+    let count = pickup_choices.0.len() as i32;
+    let width = 31;
+    let (x, y) = ((camera.width - width) / 2, (camera.height - count) / 2);
+
+    draw_batch.draw_box(Rect::with_size(x, y - 2, width, count + 3), white);
+    draw_batch.print_color(Point::new(x + 3, y - 2), "Pick up which item?", yellow);
+    draw_batch.print_color(
+        Point::new(x + 3, y + count + 1),
+        format!("{} to cancel", keymap.hint(InputAction::Cancel)),
+        yellow,
+    );
+
+    for (i, item) in pickup_choices.0.iter().enumerate() {
+        let row = y + i as i32;
+        let letter = (b'a' + i as u8) as char;
+        let name = name_query.get(*item).map_or("???", |name| name.0.as_str());
+
+        if i == cursor.index {
+            draw_batch.print_color(Point::new(x + 1, row), ">", yellow);
+        }
+        draw_batch.print_color(Point::new(x + 2, row), format!("({letter})"), yellow);
+        draw_batch.print_color(Point::new(x + 6, row), name, white);
+    }
+
+    ctx.submit_batch(20000, draw_batch);
+}
@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bracket_lib::bevy::*;
+use bracket_lib::prelude::{Rect, RGB};
+
+use crate::controller::resources::{InputAction, Keymap, LogHistory};
+use crate::model::resources::{GameLog, LogCategory};
+use crate::view::resources::MapCamera;
+use crate::view::ViewConstants;
+
+pub fn render_log_history(
+    ctx: Res<BracketContext>,
+    game_log: Res<GameLog>,
+    camera: Res<MapCamera>,
+    log_history: Res<LogHistory>,
+    keymap: Res<Keymap>,
+) {
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+
+    let black = RGB::from_f32(0.0, 0.0, 0.0);
+    let white = ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), black);
+    let yellow = ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), black);
+    let grey = ColorPair::new(RGB::from_f32(0.5, 0.5, 0.5), black);
+
+    let (width, height) = (camera.width - 1, camera.height - 1);
+    // Longest message that fits next to the turn column
+    let max_line_width = (camera.width - 10).max(1) as usize;
+
+    draw_batch.draw_box(Rect::with_size(0, 0, width, height), white);
+    draw_batch.print_color(Point::new(3, 0), "Message history", yellow);
+
+    // Filter toggles, greyed out when hidden
+    let shown = log_history.shown();
+    let mut x = 2;
+    for (i, category) in LogCategory::ALL.iter().enumerate() {
+        let label = format!("{} {}", i + 1, category.label());
+        let color = if shown.contains(category) {
+            yellow
+        } else {
+            grey
+        };
+        draw_batch.print_color(Point::new(x, 2), &label, color);
+        x += label.len() as i32 + 3;
+    }
+
+    // Newest entries at the bottom, scrolling moves the window back in time
+    let entries: Vec<_> = game_log.filtered(&shown).collect();
+    let end = entries.len().saturating_sub(log_history.scroll);
+    let start = end.saturating_sub(LogHistory::page(camera.height));
+    for (row, entry) in entries[start..end].iter().enumerate() {
+        let y = 4 + row as i32;
+        let text: String = entry.display_text().chars().take(max_line_width).collect();
+        draw_batch.print_color(Point::new(2, y), format!("{:>5}", entry.turn), grey);
+        draw_batch.print_color(Point::new(8, y), text, ColorPair::new(entry.color, black));
+    }
+
+    if entries.is_empty() {
+        draw_batch.print_color(Point::new(2, 4), "Nothing to show.", grey);
+    }
+
+    draw_batch.print_color(
+        Point::new(3, height),
+        format!(
+            "{}/{} scroll, PGUP/PGDN page, 1-5 filter, {} to close",
+            keymap.hint(InputAction::MoveNorth),
+            keymap.hint(InputAction::MoveSouth),
+            keymap.hint(InputAction::Cancel)
+        ),
+        yellow,
+    );
+
+    ctx.submit_batch(20000, draw_batch);
+}
@@ -1 +1,21 @@
+mod game_over;
+mod hud;
+mod inventory;
+mod item_menu;
+mod log_history;
+mod mouse;
+mod options;
+mod pause_menu;
+mod start_menu;
+mod targeting;
 
+pub use game_over::*;
+pub use hud::*;
+pub use inventory::*;
+pub use item_menu::*;
+pub use log_history::*;
+pub use mouse::*;
+pub use options::*;
+pub use pause_menu::*;
+pub use start_menu::*;
+pub use targeting::*;
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bracket_lib::bevy::*;
+use bracket_lib::prelude::{Rect, RGB};
+
+use super::inventory::wrap;
+use crate::controller::resources::{MouseHover, Travel};
+use crate::model::components::{Description, Name, Position};
+use crate::model::resources::Map;
+use crate::view::resources::MapCamera;
+use crate::view::ViewConstants;
+use crate::MenuState;
+
+/// Width of the text inside the tooltip
+const TOOLTIP_WIDTH: usize = 24;
+
+/// Run condition: no menu covers the map, including while monsters take their turn
+pub fn no_menu_open(menu: Option<Res<State<MenuState>>>) -> bool {
+    menu.map_or(true, |menu| *menu.get() == MenuState::Closed)
+}
+
+/// Shades the route a click would walk, or the walk already under way.
+pub fn render_path_preview(
+    ctx: Res<BracketContext>,
+    camera: Res<MapCamera>,
+    hover: Res<MouseHover>,
+    travel: Res<Travel>,
+) {
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::MAP_LAYER);
+
+    let path: Vec<Position> = if travel.is_active() {
+        travel.path.iter().copied().collect()
+    } else {
+        hover.path.clone()
+    };
+    for screen in path.iter().filter_map(|step| camera.to_screen(*step)) {
+        draw_batch.set_bg(screen, RGB::from_f32(0.2, 0.2, 0.5));
+    }
+
+    if let Some(screen) = hover.tile.and_then(|tile| camera.to_screen(tile)) {
+        draw_batch.set_bg(screen, RGB::from_f32(0.0, 0.6, 0.6));
+    }
+
+    ctx.submit_batch(15000, draw_batch);
+}
+
+/// Names and describes everything the player can see on the hovered tile.
+pub fn render_tooltip(
+    ctx: Res<BracketContext>,
+    map: Res<Map>,
+    camera: Res<MapCamera>,
+    hover: Res<MouseHover>,
+    query: Query<(&Position, &Name, Option<&Description>)>,
+) {
+    let Some(tile) = hover.tile else {
+        return;
+    };
+    let Some(screen) = camera.to_screen(tile) else {
+        return;
+    };
+    if !map.visible_tiles[map.xy_idx(tile.x, tile.y)] {
+        return;
+    }
+
+    let black = RGB::from_f32(0.0, 0.0, 0.0);
+    let white = ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), black);
+    let grey = ColorPair::new(RGB::from_f32(0.5, 0.5, 0.5), black);
+
+    let mut lines: Vec<(String, ColorPair)> = Vec::new();
+    for (_, name, description) in query.iter().filter(|(position, _, _)| **position == tile) {
+        lines.push((name.0.clone(), white));
+        if let Some(description) = description {
+            for line in wrap(&description.0, TOOLTIP_WIDTH) {
+                lines.push((line, grey));
+            }
+        }
+    }
+    if lines.is_empty() {
+        return;
+    }
+
+    // Beside the cursor, flipped to the left near the right edge
+    let width = lines.iter().map(|(line, _)| line.len()).max().unwrap_or(0) as i32 + 3;
+    let height = lines.len() as i32 + 1;
+    let x = if screen.x + width + 2 < camera.width {
+        screen.x + 2
+    } else {
+        screen.x - width - 2
+    };
+    let y = screen.y.min(camera.height - height - 1).max(0);
+
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+    draw_batch.draw_box(Rect::with_size(x, y, width, height), white);
+    for (i, (line, color)) in lines.iter().enumerate() {
+        draw_batch.print_color(Point::new(x + 2, y + 1 + i as i32), line, *color);
+    }
+
+    ctx.submit_batch(20000, draw_batch);
+}
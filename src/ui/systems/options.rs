@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bracket_lib::bevy::*;
+use bracket_lib::prelude::{Rect, RGB};
+
+use crate::controller::resources::{InputAction, Keymap, MenuCursor, OptionsEntry};
+use crate::view::resources::MapCamera;
+use crate::view::ViewConstants;
+use crate::AppSettings;
+
+pub fn render_options(
+    ctx: Res<BracketContext>,
+    settings: Res<AppSettings>,
+    camera: Res<MapCamera>,
+    cursor: Res<MenuCursor>,
+    keymap: Res<Keymap>,
+) {
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+    draw_batch.cls();
+
+    let black = RGB::from_f32(0.0, 0.0, 0.0);
+    let white = ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), black);
+    let yellow = ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), black);
+    let grey = ColorPair::new(RGB::from_f32(0.5, 0.5, 0.5), black);
+
+    // Covers the whole console so nothing under it shows through
+    let (width, height) = (camera.width - 1, camera.height - 1);
+    draw_batch.draw_box(Rect::with_size(0, 0, width, height), white);
+    draw_batch.print_color(Point::new(3, 0), "Options", yellow);
+    draw_batch.print_color(
+        Point::new(3, height),
+        format!(
+            "{}/{} to change, {} to close",
+            keymap.hint(InputAction::MenuLeft),
+            keymap.hint(InputAction::MenuRight),
+            keymap.hint(InputAction::Cancel)
+        ),
+        yellow,
+    );
+
+    let count = OptionsEntry::ALL.len() as i32;
+    let (x, y) = ((width - 30) / 2, (height - count * 2) / 2);
+    for (i, entry) in OptionsEntry::ALL.iter().enumerate() {
+        let row = y + i as i32 * 2;
+        let color = if i == cursor.index {
+            draw_batch.print_color(Point::new(x - 2, row), ">", yellow);
+            yellow
+        } else {
+            white
+        };
+        draw_batch.print_color(Point::new(x, row), entry.label(), color);
+        draw_batch.print_color(Point::new(x + 20, row), entry.value(&settings), grey);
+    }
+
+    ctx.submit_batch(20000, draw_batch);
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use bracket_lib::bevy::*;
+use bracket_lib::prelude::{Rect, RGB};
+
+use crate::controller::resources::{InputAction, Keymap, MenuCursor, PauseEntry};
+use crate::view::resources::MapCamera;
+use crate::view::ViewConstants;
+
+pub fn render_pause_menu(
+    ctx: Res<BracketContext>,
+    camera: Res<MapCamera>,
+    cursor: Res<MenuCursor>,
+    keymap: Res<Keymap>,
+) {
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+    draw_batch.cls();
+
+    let black = RGB::from_f32(0.0, 0.0, 0.0);
+    let white = ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), black);
+    let yellow = ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), black);
+
+    let count = PauseEntry::ALL.len() as i32;
+    let (width, height) = (30, count * 2 + 3);
+    let x = (camera.width - width) / 2;
+    let y = (camera.height - height) / 2;
+
+    draw_batch.draw_box(Rect::with_size(x, y, width, height), white);
+    draw_batch.print_color(Point::new(x + 3, y), "Paused", yellow);
+    draw_batch.print_color(
+        Point::new(x + 3, y + height),
+        format!("{} to resume", keymap.hint(InputAction::Cancel)),
+        yellow,
+    );
+
+    for (i, entry) in PauseEntry::ALL.iter().enumerate() {
+        let row = y + 2 + i as i32 * 2;
+        let color = if i == cursor.index {
+            draw_batch.print_color(Point::new(x + 2, row), ">", yellow);
+            yellow
+        } else {
+            white
+        };
+        draw_batch.print_color(Point::new(x + 4, row), entry.label(), color);
+    }
+
+    ctx.submit_batch(20000, draw_batch);
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use bracket_lib::bevy::*;
+use bracket_lib::prelude::RGB;
+
+use crate::controller::resources::{Keymap, MainMenuEntry, MenuCursor};
+use crate::model::commands::save_exists;
+use crate::model::resources::SaveStatus;
+use crate::view::ViewConstants;
+use crate::AppConstants;
+
+pub fn render_start_menu(
+    ctx: Res<BracketContext>,
+    save_status: Res<SaveStatus>,
+    cursor: Res<MenuCursor>,
+    keymap: Res<Keymap>,
+) {
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+    draw_batch.cls();
+
+    let black = RGB::from_f32(0.0, 0.0, 0.0);
+    let white = ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), black);
+    let grey = ColorPair::new(RGB::from_f32(0.5, 0.5, 0.5), black);
+
+    draw_batch.print_color_centered(
+        15,
+        AppConstants::APP_NAME,
+        ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), black),
+    );
+
+    let yellow = ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), black);
+
+    for (i, entry) in MainMenuEntry::ALL.iter().enumerate() {
+        let color = if i == cursor.index {
+            yellow
+        } else if *entry == MainMenuEntry::Continue && !save_exists() {
+            // Greyed out when there is nothing to continue
+            grey
+        } else {
+            white
+        };
+        draw_batch.print_color_centered(
+            20 + i as i32 * 2,
+            format!("({}) {}", keymap.hint(entry.action()), entry.label()),
+            color,
+        );
+    }
+
+    if let Some(message) = &save_status.message {
+        draw_batch.print_color_centered(
+            30,
+            message,
+            ColorPair::new(RGB::from_f32(1.0, 0.3, 0.3), black),
+        );
+    }
+
+    ctx.submit_batch(0, draw_batch);
+}
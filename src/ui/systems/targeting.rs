@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bracket_lib::bevy::*;
+use bracket_lib::prelude::{DistanceAlg, RGB};
+
+use crate::controller::resources::{InputAction, Keymap, Targeting};
+use crate::model::components::{Player, Position};
+use crate::model::resources::Map;
+use crate::view::resources::MapCamera;
+use crate::view::ViewConstants;
+
+pub fn render_targeting(
+    ctx: Res<BracketContext>,
+    map: Res<Map>,
+    camera: Res<MapCamera>,
+    targeting: Res<Targeting>,
+    keymap: Res<Keymap>,
+    player_query: Query<&Position, With<Player>>,
+) {
+    let Ok(player_position) = player_query.get_single() else {
+        return;
+    };
+
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::MAP_LAYER);
+    let origin = Point::new(player_position.x, player_position.y);
+
+    // Shade every visible tile the item can reach
+    for y in player_position.y - targeting.range..=player_position.y + targeting.range {
+        for x in player_position.x - targeting.range..=player_position.x + targeting.range {
+            if !map.in_bounds(x, y) || !map.visible_tiles[map.xy_idx(x, y)] {
+                continue;
+            }
+            let Some(screen) = camera.to_screen(Position::new(x, y)) else {
+                continue;
+            };
+            if DistanceAlg::Pythagoras.distance2d(origin, Point::new(x, y))
+                <= targeting.range as f32
+            {
+                draw_batch.set_bg(screen, RGB::from_f32(0.0, 0.0, 0.6));
+            }
+        }
+    }
+
+    if let Some(screen) = camera.to_screen(targeting.cursor) {
+        draw_batch.set_bg(screen, RGB::from_f32(0.0, 0.8, 0.8));
+    }
+
+    ctx.submit_batch(15000, draw_batch);
+
+    let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
+    draw_batch.print_color(
+        Point::new(1, 0),
+        format!(
+            "Select target: {} to confirm, {} to cancel",
+            keymap.hint(InputAction::Confirm),
+            keymap.hint(InputAction::Cancel)
+        ),
+        ColorPair::new(RGB::from_f32(1.0, 1.0, 0.0), RGB::from_f32(0.0, 0.0, 0.0)),
+    );
+
+    ctx.submit_batch(20000, draw_batch);
+}
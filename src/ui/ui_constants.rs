@@ -8,8 +8,13 @@ impl UiConstants {
     /// UI layout controlling the width of the stats panel
     pub const STATS_WIDTH: u32 = 300;
 
-    /// The maximum number of messages to display in the `GameLog`
-    pub const LOG_LIMIT: usize = 50;
+    /// Text size used by the panels
+    pub const FONT_SIZE: f32 = 14.0;
+    /// Height of one line of text in the `GameLog`
+    pub const LOG_LINE_HEIGHT: f32 = 17.0;
+    /// Space between the panel edges and their contents
+    pub const PANEL_PADDING: f32 = 6.0;
+
     /// The background color of the `GameLog`
     pub const LOG_BACKGROUND: Srgba = Srgba::new(0.2, 0.2, 0.2, 1.0);
     pub const LOG_FOREGROUND: Srgba = Srgba::new(0.0, 1.0, 0.0, 1.0);
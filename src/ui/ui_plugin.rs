@@ -1,6 +1,94 @@
 use bevy::prelude::*;
 
+use crate::ui::systems::*;
+use crate::view::systems::render_system;
+use crate::AppSet;
+use crate::MenuState;
+use crate::OptionsState;
+use crate::RunningState;
+
 pub struct UiPlugin;
 impl Plugin for UiPlugin {
-    fn build(&self, _app: &mut App) {}
+    fn build(&self, app: &mut App) {
+        // Register systems
+        app.add_systems(Startup, (spawn_ui_camera, spawn_hud).chain())
+            .add_systems(
+                Update,
+                (
+                    sync_hud_visibility.run_if(state_changed::<RunningState>),
+                    fit_map_viewport,
+                    update_stats_panel,
+                    update_log_panel,
+                )
+                    .in_set(AppSet::Render),
+            )
+            .add_systems(
+                Update,
+                render_start_menu
+                    .in_set(AppSet::Render)
+                    .run_if(in_state(RunningState::StartMenu).and(in_state(OptionsState::Closed))),
+            )
+            .add_systems(
+                Update,
+                render_pause_menu
+                    .in_set(AppSet::Render)
+                    .run_if(in_state(RunningState::Paused).and(in_state(OptionsState::Closed))),
+            )
+            .add_systems(
+                Update,
+                render_options
+                    .in_set(AppSet::Render)
+                    .after(render_system)
+                    .run_if(in_state(OptionsState::Open)),
+            )
+            .add_systems(
+                Update,
+                render_game_over
+                    .in_set(AppSet::Render)
+                    .run_if(in_state(RunningState::GameOver)),
+            )
+            .add_systems(
+                Update,
+                (render_path_preview, render_tooltip)
+                    .chain()
+                    .in_set(AppSet::Render)
+                    .after(render_system)
+                    .run_if(in_state(RunningState::Running).and(no_menu_open)),
+            )
+            .add_systems(
+                Update,
+                render_pickup_menu
+                    .in_set(AppSet::Render)
+                    .after(render_system)
+                    .run_if(in_state(MenuState::PickupItem)),
+            )
+            .add_systems(
+                Update,
+                render_inventory
+                    .in_set(AppSet::Render)
+                    .after(render_system)
+                    .run_if(in_state(MenuState::Inventory)),
+            )
+            .add_systems(
+                Update,
+                render_equipment
+                    .in_set(AppSet::Render)
+                    .after(render_system)
+                    .run_if(in_state(MenuState::Equipment)),
+            )
+            .add_systems(
+                Update,
+                render_log_history
+                    .in_set(AppSet::Render)
+                    .after(render_system)
+                    .run_if(in_state(MenuState::LogHistory)),
+            )
+            .add_systems(
+                Update,
+                render_targeting
+                    .in_set(AppSet::Render)
+                    .after(render_system)
+                    .run_if(in_state(MenuState::Targeting)),
+            );
+    }
 }
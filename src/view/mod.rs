@@ -1,3 +1,4 @@
+pub mod resources;
 pub mod systems;
 
 mod view_constants;
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bracket_lib::prelude::Point;
+
+use crate::model::components::Position;
+use crate::model::resources::Map;
+
+/// The part of the map drawn into the console, following the player.
+///
+/// Everything that draws map tiles goes through `to_screen`, and the mouse through `to_world`.
+/// It covers the whole console, so menus lay themselves out by its size too.
+#[derive(Resource, Debug, Clone)]
+pub struct MapCamera {
+    /// Map position shown in the top left corner of the console
+    pub origin: Position,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl MapCamera {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            origin: Position::default(),
+            width,
+            height,
+        }
+    }
+
+    /// Centres the view on `target` without scrolling past the map edges
+    pub fn center_on(&mut self, target: Position, map: &Map) {
+        let origin = Position::new(
+            Self::clamp_axis(target.x - self.width / 2, map.width, self.width),
+            Self::clamp_axis(target.y - self.height / 2, map.height, self.height),
+        );
+        if self.origin != origin {
+            self.origin = origin;
+        }
+    }
+
+    /// Maps smaller than the view sit in its middle instead of scrolling
+    fn clamp_axis(start: i32, map_size: i32, view_size: i32) -> i32 {
+        if map_size <= view_size {
+            -(view_size - map_size) / 2
+        } else {
+            start.clamp(0, map_size - view_size)
+        }
+    }
+
+    /// Console cell of a map position, `None` when it is out of view
+    pub fn to_screen(&self, position: Position) -> Option<Point> {
+        let x = position.x - self.origin.x;
+        let y = position.y - self.origin.y;
+        (x >= 0 && x < self.width && y >= 0 && y < self.height).then(|| Point::new(x, y))
+    }
+
+    /// Map position under a console cell
+    pub fn to_world(&self, screen: Point) -> Position {
+        Position::new(screen.x + self.origin.x, screen.y + self.origin.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera_on(target: Position, map: &Map) -> MapCamera {
+        let mut camera = MapCamera::new(60, 40);
+        camera.center_on(target, map);
+        camera
+    }
+
+    #[test]
+    fn larger_maps_scroll_without_passing_the_edges() {
+        let map = Map::new(100, 80);
+
+        assert_eq!(
+            camera_on(Position::new(50, 40), &map).origin,
+            Position::new(20, 20)
+        );
+        assert_eq!(
+            camera_on(Position::new(0, 0), &map).origin,
+            Position::new(0, 0)
+        );
+        assert_eq!(
+            camera_on(Position::new(99, 79), &map).origin,
+            Position::new(40, 40)
+        );
+    }
+
+    #[test]
+    fn smaller_maps_sit_in_the_middle() {
+        let map = Map::new(20, 20);
+
+        for target in [Position::new(0, 0), Position::new(19, 19)] {
+            let camera = camera_on(target, &map);
+            assert_eq!(camera.origin, Position::new(-20, -10));
+            assert_eq!(
+                camera.to_screen(Position::new(0, 0)),
+                Some(Point::new(20, 10))
+            );
+            assert_eq!(
+                camera.to_screen(Position::new(19, 19)),
+                Some(Point::new(39, 29))
+            );
+        }
+    }
+
+    #[test]
+    fn screen_positions_round_trip() {
+        let map = Map::new(100, 80);
+        let camera = camera_on(Position::new(70, 30), &map);
+
+        for x in camera.origin.x..camera.origin.x + camera.width {
+            for y in camera.origin.y..camera.origin.y + camera.height {
+                let position = Position::new(x, y);
+                let screen = camera.to_screen(position).expect("in view");
+                assert_eq!(camera.to_world(screen), position);
+            }
+        }
+
+        let past_the_corner = Position::new(camera.origin.x + camera.width, camera.origin.y);
+        assert_eq!(camera.to_screen(past_the_corner), None);
+        assert_eq!(
+            camera.to_screen(Position::new(camera.origin.x - 1, 0)),
+            None
+        );
+    }
+}
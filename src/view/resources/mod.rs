@@ -0,0 +1,5 @@
+mod map_camera;
+mod tileset;
+
+pub use map_camera::*;
+pub use tileset::*;
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use bracket_lib::prelude::{to_cp437, FontCharType};
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use crate::model::components::TerrainType;
+use crate::AppConstants;
+
+/// Sprite of each terrain type
+#[derive(Debug, Clone, Deserialize)]
+pub struct TerrainSprites {
+    pub floor: FontCharType,
+    pub wall: FontCharType,
+    pub down_stairs: FontCharType,
+    pub up_stairs: FontCharType,
+    #[serde(default = "TerrainSprites::default_door")]
+    pub door: FontCharType,
+}
+
+impl TerrainSprites {
+    fn default_door() -> FontCharType {
+        to_cp437('+')
+    }
+}
+
+/// Sprite sheet and sprite indices used instead of font glyphs when the tileset renderer is on.
+///
+/// Only present as a resource in tileset mode.
+#[derive(Resource, Debug, Clone, Deserialize)]
+pub struct Tileset {
+    /// Sprite sheet, relative to the assets folder
+    pub image: String,
+    /// Size in pixels of one sprite in the sheet
+    pub tile_size: (u32, u32),
+    /// Sprites per row and column of the sheet
+    pub grid: (u16, u16),
+    pub terrain: TerrainSprites,
+    #[serde(default)]
+    pub glyphs: HashMap<char, FontCharType>,
+}
+
+impl Tileset {
+    /// Reads a tileset data file from the assets folder, or says why it can't
+    pub fn load(path: &str) -> Result<Self, String> {
+        let mut file_path = PathBuf::from(AppConstants::BASE);
+        file_path.push(path);
+
+        let contents = fs::read_to_string(&file_path)
+            .map_err(|err| format!("Failed to read tileset {}: {err}", file_path.display()))?;
+        toml::from_str(&contents)
+            .map_err(|err| format!("Failed to parse tileset {}: {err}", file_path.display()))
+    }
+
+    pub fn terrain(&self, terrain: &TerrainType) -> FontCharType {
+        match terrain {
+            TerrainType::Floor => self.terrain.floor,
+            TerrainType::Wall => self.terrain.wall,
+            TerrainType::DownStairs => self.terrain.down_stairs,
+            TerrainType::UpStairs => self.terrain.up_stairs,
+            TerrainType::Door => self.terrain.door,
+        }
+    }
+
+    pub fn glyph(&self, glyph: char) -> FontCharType {
+        self.glyphs
+            .get(&glyph)
+            .copied()
+            .unwrap_or_else(|| to_cp437(glyph))
+    }
+}
@@ -1,3 +1,5 @@
 mod render;
+mod window;
 
 pub use render::*;
+pub use window::*;
@@ -2,19 +2,43 @@ use bevy::prelude::*;
 use bracket_lib::bevy::*;
 use bracket_lib::prelude::{FontCharType, RGB};
 
-use crate::model::components::{Position, Renderable, TerrainType};
+use crate::model::components::{Player, Position, Renderable, TerrainType};
 use crate::model::resources::Map;
+use crate::view::resources::{MapCamera, Tileset};
+use crate::view::ViewConstants;
 use crate::RunningState;
 
-pub fn render_map(map: &Res<Map>, ctx: &Res<BracketContext>) {
+/// Keeps the camera on the player.
+pub fn update_camera(
+    map: Res<Map>,
+    player_query: Query<&Position, With<Player>>,
+    mut camera: ResMut<MapCamera>,
+) {
+    if let Ok(player_position) = player_query.get_single() {
+        camera.center_on(*player_position, &map);
+    }
+}
+
+pub fn render_map(
+    map: &Res<Map>,
+    camera: &Res<MapCamera>,
+    tileset: Option<&Tileset>,
+    ctx: &Res<BracketContext>,
+) {
     let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::MAP_LAYER);
 
     // Clear the console
     draw_batch.cls();
 
-    // Render the map
-    for y in 0..map.height {
-        for x in 0..map.width {
+    // Render the part of the map in view
+    for screen_y in 0..camera.height {
+        for screen_x in 0..camera.width {
+            let screen = Point::new(screen_x, screen_y);
+            let Position { x, y } = camera.to_world(screen);
+            if !map.in_bounds(x, y) {
+                continue;
+            }
             let idx = map.xy_idx(x, y);
             let tile = &map.tiles[idx];
 
@@ -33,18 +57,32 @@ pub fn render_map(map: &Res<Map>, ctx: &Res<BracketContext>) {
                         glyph = to_cp437('.');
                         fg = RGB::from_f32(0.3, 0.3, 0.3);
                     }
-                    TerrainType::Stairs => {
+                    TerrainType::DownStairs => {
                         glyph = to_cp437('>');
                         fg = RGB::from_f32(1.0, 1.0, 1.0);
                     }
+                    TerrainType::UpStairs => {
+                        glyph = to_cp437('<');
+                        fg = RGB::from_f32(1.0, 1.0, 1.0);
+                    }
+                    TerrainType::Door => {
+                        glyph = to_cp437('+');
+                        fg = RGB::from_f32(0.6, 0.4, 0.2);
+                    }
                 }
 
+                // Sprites keep their own colours, only the brightness changes
+                let (glyph, fg) = match tileset {
+                    Some(tileset) => (tileset.terrain(tile), RGB::from_f32(0.67, 0.67, 0.67)),
+                    None => (glyph, fg),
+                };
+
                 // If the tile is currently visible, use brighter colors
                 if map.visible_tiles[idx] {
-                    draw_batch.set(Point::new(x, y), ColorPair::new(fg * 1.5, bg), glyph);
+                    draw_batch.set(screen, ColorPair::new(fg * 1.5, bg), glyph);
                 } else {
                     // Dimmer colors for explored but not visible tiles
-                    draw_batch.set(Point::new(x, y), ColorPair::new(fg * 0.5, bg), glyph);
+                    draw_batch.set(screen, ColorPair::new(fg * 0.5, bg), glyph);
                 }
             }
         }
@@ -55,10 +93,13 @@ pub fn render_map(map: &Res<Map>, ctx: &Res<BracketContext>) {
 
 pub fn render_entities(
     map: &Res<Map>,
+    camera: &Res<MapCamera>,
+    tileset: Option<&Tileset>,
     ctx: &Res<BracketContext>,
     query: &Query<(&Position, &Renderable)>,
 ) {
     let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::MAP_LAYER);
 
     // Sort entities by render order
     let mut entities: Vec<(&Position, &Renderable)> = query.iter().collect();
@@ -66,30 +107,41 @@ pub fn render_entities(
 
     // Render entities
     for (pos, render) in entities {
+        let Some(screen) = camera.to_screen(*pos) else {
+            continue;
+        };
         let idx = map.xy_idx(pos.x, pos.y);
 
         // Only render if the entity is in a visible tile
         if map.visible_tiles[idx] {
-            draw_batch.set(
-                Point::new(pos.x, pos.y),
-                ColorPair::new(render.fg, render.bg),
-                to_cp437(render.glyph),
-            );
+            match tileset {
+                Some(tileset) => draw_batch.set(
+                    screen,
+                    ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), render.bg),
+                    tileset.glyph(render.glyph),
+                ),
+                None => draw_batch.set(
+                    screen,
+                    ColorPair::new(render.fg, render.bg),
+                    to_cp437(render.glyph),
+                ),
+            };
         }
     }
 
     ctx.submit_batch(5000, draw_batch);
 }
 
-pub fn render_ui(map: Res<Map>, ctx: Res<BracketContext>) {
+pub fn render_ui(ctx: Res<BracketContext>, camera: &MapCamera) {
     let mut draw_batch = ctx.new_draw_batch();
+    draw_batch.target(ViewConstants::TEXT_LAYER);
 
     // Clear the console
-    // draw_batch.cls();
+    draw_batch.cls();
 
-    // Draw a box around the map
+    // Draw a box around the view
     draw_batch.draw_hollow_box(
-        bracket_lib::prelude::Rect::with_size(0, 0, map.width - 1, map.height - 1),
+        bracket_lib::prelude::Rect::with_size(0, 0, camera.width - 1, camera.height - 1),
         ColorPair::new(RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0)),
     );
 
@@ -100,11 +152,18 @@ pub fn render_ui(map: Res<Map>, ctx: Res<BracketContext>) {
 
 pub fn render_system(
     map: Res<Map>,
+    camera: Res<MapCamera>,
+    tileset: Option<Res<Tileset>>,
     ctx: Res<BracketContext>,
     query: Query<(&Position, &Renderable)>,
     state: Res<State<RunningState>>,
 ) {
     if *state.get() != RunningState::Running {
+        // Menus only draw on the text layer, don't leave the last map under them
+        let mut draw_batch = ctx.new_draw_batch();
+        draw_batch.target(ViewConstants::MAP_LAYER);
+        draw_batch.cls();
+        ctx.submit_batch(0, draw_batch);
         return;
     }
 
@@ -112,9 +171,10 @@ pub fn render_system(
     ctx.cls();
 
     // Render map, entities, and UI
-    render_map(&map, &ctx);
-    render_entities(&map, &ctx, &query);
-    render_ui(map, ctx);
+    let tileset = tileset.as_deref();
+    render_map(&map, &camera, tileset, &ctx);
+    render_entities(&map, &camera, tileset, &ctx, &query);
+    render_ui(ctx, &camera);
 
     // Present the frame
     // ctx.present().expect("Failed to present frame");
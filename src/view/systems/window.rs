@@ -0,0 +1,36 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+
+use crate::ui::UiConstants;
+use crate::view::resources::MapCamera;
+use crate::AppSettings;
+
+/// Resizes the window and switches fullscreen when the settings are edited in game.
+///
+/// The window always fits the console it was started with.
+pub fn apply_window_settings(
+    settings: Res<AppSettings>,
+    camera: Res<MapCamera>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+
+    let mode = if settings.fullscreen() {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    if window.mode != mode {
+        window.mode = mode;
+    }
+
+    let width = (settings.tile_size * camera.width as u32 + UiConstants::STATS_WIDTH) as f32;
+    let height = (settings.tile_size * camera.height as u32 + UiConstants::LOG_HEIGHT) as f32;
+    if window.resolution.width() != width || window.resolution.height() != height {
+        window.resolution.set(width, height);
+    }
+}
@@ -2,4 +2,9 @@ pub struct ViewConstants;
 
 impl ViewConstants {
     pub const TILE_SIZE: f32 = 8.0;
+
+    /// Console the map is drawn on, with the tileset in tileset mode
+    pub const MAP_LAYER: usize = 0;
+    /// Console drawn over the map for menus and other text, always using the font
+    pub const TEXT_LAYER: usize = 1;
 }
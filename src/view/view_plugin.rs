@@ -1,18 +1,78 @@
 use bevy::prelude::*;
 use bracket_lib::bevy::BTermBuilder;
 
-use super::systems::render_system;
+use super::resources::{MapCamera, Tileset};
+use super::systems::{apply_window_settings, render_system, update_camera};
 use crate::AppSet;
+use crate::AppSettings;
+use crate::RenderMode;
 use crate::RunningState;
 
 pub struct ViewPlugin;
 impl Plugin for ViewPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(BTermBuilder::simple_80x50()).add_systems(
-            Update,
-            render_system
-                .run_if(not(in_state(RunningState::Paused)))
-                .in_set(AppSet::Render),
-        );
+        let settings = app
+            .world()
+            .get_resource::<AppSettings>()
+            .cloned()
+            .unwrap_or_default();
+
+        // Falls back to the font when the tileset can't be loaded
+        let tileset = match settings.render_mode {
+            RenderMode::Tileset => Tileset::load(&settings.tileset_path)
+                .map_err(|err| warn!("{err}, drawing the map with the font"))
+                .ok(),
+            RenderMode::Ascii => None,
+        };
+
+        let (width, height) = (settings.view_size.0 as i32, settings.view_size.1 as i32);
+        app.add_plugins(build_terminal(&settings, tileset.as_ref(), width, height))
+            .insert_resource(MapCamera::new(width, height))
+            .add_systems(
+                Update,
+                (
+                    update_camera.run_if(in_state(RunningState::Running)),
+                    render_system.run_if(not(in_state(RunningState::Paused))),
+                )
+                    .chain()
+                    .in_set(AppSet::Render),
+            )
+            .add_systems(
+                Update,
+                apply_window_settings.run_if(resource_changed::<AppSettings>),
+            );
+
+        if let Some(tileset) = tileset {
+            app.insert_resource(tileset);
+        }
+    }
+}
+
+/// A map console and a sparse text console on top, see `ViewConstants::MAP_LAYER`.
+///
+/// Both are `width` by `height` tiles, filling the window left of and above the HUD.
+fn build_terminal(
+    settings: &AppSettings,
+    tileset: Option<&Tileset>,
+    width: i32,
+    height: i32,
+) -> BTermBuilder {
+    let font_size = (settings.font_width as f32, settings.font_height as f32);
+
+    match tileset {
+        Some(tileset) => BTermBuilder::empty()
+            .with_font(
+                &tileset.image,
+                tileset.grid.0,
+                tileset.grid.1,
+                (tileset.tile_size.0 as f32, tileset.tile_size.1 as f32),
+            )
+            .with_font(&settings.font_path, 16, 16, font_size)
+            .with_simple_console(0, width, height)
+            .with_sparse_console(1, width, height),
+        None => BTermBuilder::empty()
+            .with_font(&settings.font_path, 16, 16, font_size)
+            .with_simple_console(0, width, height)
+            .with_sparse_console(0, width, height),
     }
 }